@@ -1,10 +1,13 @@
 //! Ejemplo de uso de múltiples overlays con la librería subs_overlay
 
 use std::{error::Error, thread, time::Duration};
-use subs_overlay_lib::{OverlayManager, OverlayConfig, TextConfig};
+use subs_overlay_lib::{init_ui_thread, OverlayManager, OverlayConfig, TextConfig};
 use std::sync::{Arc, Mutex};
 
 fn main() -> Result<(), Box<dyn Error>> {
+    // Este hilo ejecutará el event loop de Slint
+    init_ui_thread();
+
     // Crear un gestor de overlays
     let manager = Arc::new(Mutex::new(OverlayManager::new()));
 
@@ -14,6 +17,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         font_size: 20.0,
         color: "#FFFF00".to_string(), // Amarillo
         position: (1400, 20),
+        ..Default::default()
     };
 
     let notification_overlay_config = OverlayConfig {
@@ -23,6 +27,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         transparent: true,
         always_on_top: true,
         ignore_input: true,
+        ..Default::default()
     };
 
     let notification_id = {
@@ -40,6 +45,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         font_size: 24.0,
         color: "#FFFFFF".to_string(), // Blanco
         position: (300, 800),
+        ..Default::default()
     };
 
     let subtitle_overlay_config = OverlayConfig {
@@ -49,6 +55,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         transparent: true,
         always_on_top: true,
         ignore_input: true,
+        ..Default::default()
     };
 
     let subtitle_id = {
@@ -66,6 +73,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         font_size: 16.0,
         color: "#00FF00".to_string(), // Verde
         position: (10, 10),
+        ..Default::default()
     };
 
     let system_info_overlay_config = OverlayConfig {
@@ -75,6 +83,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         transparent: true,
         always_on_top: true,
         ignore_input: true,
+        ..Default::default()
     };
 
     let system_info_id = {
@@ -89,7 +98,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     // Listar todos los overlays activos
     {
         let manager = manager.lock().unwrap();
-        let overlays = manager.list_overlays();
+        let overlays = manager.list_overlays_ordered()?;
         println!("Overlays activos:");
         for id in &overlays {
             println!("  - {}", id);
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use slint::ComponentHandle;
+
+use crate::tasks::{self, TaskKind};
+use crate::{color_utils, lock_applied, window_manager, OverlayError, OverlayEvent, OverlayId, OverlayManager};
+
+/// Interval between animation frames (~60 fps)
+const FRAME: Duration = Duration::from_millis(16);
+
+/// Stops a running color animation of an overlay. Must run on the UI thread.
+pub(crate) fn cancel_color_animation(overlay_id: &OverlayId) {
+    tasks::cancel(overlay_id, TaskKind::ColorAnimation);
+}
+
+/// Window alpha `t` of the way (0 to 1) through fading out from `start`
+fn fade_out_alpha(start: u8, t: f32) -> u8 {
+    (start as f32 * (1.0 - t.clamp(0.0, 1.0))).round() as u8
+}
+
+impl OverlayManager {
+    /// Fades the text color from its current value to `to` over `duration`, interpolating in
+    /// linear RGB (alpha included). Starting another animation or changing the color through
+    /// `update_config` cancels the one in progress.
+    pub fn animate_color(&self, overlay_id: &OverlayId, to: String, duration: Duration) -> Result<(), OverlayError> {
+        let target = color_utils::parse_argb(&to).ok_or_else(|| OverlayError::InvalidColor(to.clone()))?;
+
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get_mut(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+        if overlay.config.locked {
+            return Err(OverlayError::Locked(overlay_id.clone()));
+        }
+
+        let from = lock_applied(&overlay.applied)
+            .color
+            .unwrap_or_else(|| overlay.config.text_color_argb());
+        overlay.config.text.color = to;
+        let event = OverlayEvent::ConfigUpdated {
+            id: overlay_id.clone(),
+            config: overlay.config.clone(),
+        };
+        let applied = Arc::clone(&overlay.applied);
+        let id = overlay_id.clone();
+
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            let started = Instant::now();
+            let weak = window.as_weak();
+            let tick_id = id.clone();
+            let timer = slint::Timer::default();
+            timer.start(slint::TimerMode::Repeated, FRAME, move || {
+                let Some(window) = weak.upgrade() else {
+                    return;
+                };
+                let t = if duration.is_zero() {
+                    1.0
+                } else {
+                    started.elapsed().as_secs_f32() / duration.as_secs_f32()
+                };
+                lock_applied(&applied).set_color(&window, color_utils::lerp_argb(from, target, t));
+                if t >= 1.0 {
+                    tasks::with_task(&tick_id, TaskKind::ColorAnimation, slint::Timer::stop);
+                }
+            });
+
+            tasks::register(&id, TaskKind::ColorAnimation, timer);
+        })?;
+        drop(overlays);
+
+        self.notify(event);
+        Ok(())
+    }
+
+    /// Fades the overlay out over `fade_ms` milliseconds, then removes it as `remove_overlay`
+    /// does, for polished dismissals of notifications. The window is kept alive until the
+    /// fade ends; removing the overlay before that ends it early. Locked overlays fail with
+    /// `Locked` right away.
+    pub fn remove_overlay_animated(&self, overlay_id: &OverlayId, fade_ms: u64) -> Result<(), OverlayError> {
+        let (window_weak, start_alpha) = {
+            let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+            let overlay = overlays
+                .get(overlay_id)
+                .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+            if overlay.config.locked {
+                return Err(OverlayError::Locked(overlay_id.clone()));
+            }
+            (overlay.window_weak.clone(), overlay.config.window_alpha)
+        };
+        let fade = Duration::from_millis(fade_ms);
+        let manager = self.clone();
+        let id = overlay_id.clone();
+
+        crate::invoke_on_ui_thread(move || {
+            let remove = move |manager: &OverlayManager, id: &OverlayId| {
+                if let Err(e) = manager.remove_overlay(id) {
+                    overlay_log!(warn, id, "Could not remove faded-out overlay: {}", e);
+                }
+            };
+            // Nothing left to fade
+            let Some(window) = window_weak.upgrade() else {
+                return remove(&manager, &id);
+            };
+
+            let started = Instant::now();
+            let tick_id = id.clone();
+            let timer = slint::Timer::default();
+            timer.start(slint::TimerMode::Repeated, FRAME, move || {
+                let t = if fade.is_zero() {
+                    1.0
+                } else {
+                    started.elapsed().as_secs_f32() / fade.as_secs_f32()
+                };
+                // The timer owns a strong handle, so the window outlives the fade
+                if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                    let _ = window_manager::set_window_transparency(hwnd, fade_out_alpha(start_alpha, t));
+                }
+                if t >= 1.0 {
+                    tasks::with_task(&tick_id, TaskKind::FadeOut, slint::Timer::stop);
+                    // Removal drops this timer, so it waits until the callback has returned
+                    let (manager, id) = (manager.clone(), tick_id.clone());
+                    slint::Timer::single_shot(Duration::ZERO, move || remove(&manager, &id));
+                }
+            });
+            tasks::register(&id, TaskKind::FadeOut, timer);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fade_out_alpha() {
+        assert_eq!(fade_out_alpha(255, 0.0), 255);
+        assert_eq!(fade_out_alpha(200, 0.5), 100);
+        assert_eq!(fade_out_alpha(255, 1.0), 0);
+        // Frames landing past the end stay fully faded
+        assert_eq!(fade_out_alpha(255, 1.3), 0);
+    }
+}
@@ -0,0 +1,308 @@
+//! Audio-reactive overlays, enabled with the `audio` feature.
+//!
+//! An overlay bound with `OverlayManager::bind_audio_reactivity` follows the level of a
+//! microphone (or, on Windows, of what the speakers are playing): its window opacity or font
+//! size rises with the amplitude, e.g. for a caption that "talks" along with the streamer.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, Sample, SampleFormat, SizedSample};
+use slint::{ComponentHandle, Weak};
+
+use crate::tasks::{self, TaskKind};
+use crate::{lock_applied, window_manager, AppliedProps, OverlayError, OverlayId, OverlayManager, OverlayUI};
+
+/// Interval between level updates (~30 fps)
+const FRAME: Duration = Duration::from_millis(33);
+
+/// Where the audio level is read from
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioSource {
+    /// The default microphone
+    DefaultInput,
+    /// Whatever the default output device is playing (WASAPI loopback, Windows only)
+    Loopback,
+    /// An input device by the name the OS reports for it
+    Device(String),
+}
+
+/// What the audio level drives
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AudioTarget {
+    /// Window opacity, from `min` (0..1, of the configured `window_alpha`) in silence up to full
+    Opacity { min: f32 },
+    /// Font size, from the configured size in silence up to `max_scale` times it
+    FontSize { max_scale: f32 },
+}
+
+/// Settings for `OverlayManager::bind_audio_reactivity`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioReactivity {
+    pub source: AudioSource,
+    pub target: AudioTarget,
+    /// Gain applied to the RMS level before it's clamped to 0..1; speech rarely goes past
+    /// 0.25 RMS, hence the default of 4
+    pub sensitivity: f32,
+    /// How slowly the effect follows the level, from 0 (instantly) towards 1 (default 0.8)
+    pub smoothing: f32,
+}
+
+impl Default for AudioReactivity {
+    fn default() -> Self {
+        Self {
+            source: AudioSource::DefaultInput,
+            target: AudioTarget::Opacity { min: 0.3 },
+            sensitivity: 4.0,
+            smoothing: 0.8,
+        }
+    }
+}
+
+/// Smooths raw levels into a 0..1 intensity
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LevelFollower {
+    sensitivity: f32,
+    smoothing: f32,
+    level: f32,
+}
+
+impl LevelFollower {
+    fn new(sensitivity: f32, smoothing: f32) -> Self {
+        let smoothing = if smoothing.is_nan() { 0.0 } else { smoothing.clamp(0.0, 0.99) };
+        Self { sensitivity: sensitivity.max(0.0), smoothing, level: 0.0 }
+    }
+
+    fn update(&mut self, rms: f32) -> f32 {
+        let input = (rms * self.sensitivity).clamp(0.0, 1.0);
+        self.level = self.smoothing * self.level + (1.0 - self.smoothing) * input;
+        self.level
+    }
+}
+
+fn rms<T>(samples: &[T]) -> f32
+where
+    T: Sample,
+    f32: FromSample<T>,
+{
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum: f32 = samples
+        .iter()
+        .map(|&sample| {
+            let value: f32 = sample.to_sample();
+            value * value
+        })
+        .sum();
+    (sum / samples.len() as f32).sqrt()
+}
+
+fn audio_error(error: impl std::fmt::Display) -> OverlayError {
+    OverlayError::Audio(error.to_string())
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    level: Arc<AtomicU32>,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            level.store(rms(data).to_bits(), Ordering::Relaxed);
+        },
+        |e| log::warn!("Audio stream error: {}", e),
+        None,
+    )
+}
+
+fn open_stream(source: &AudioSource, level: Arc<AtomicU32>) -> Result<cpal::Stream, OverlayError> {
+    let host = cpal::default_host();
+    let (device, config) = match source {
+        AudioSource::DefaultInput => {
+            let device = host
+                .default_input_device()
+                .ok_or_else(|| audio_error("no default input device"))?;
+            let config = device.default_input_config().map_err(audio_error)?;
+            (device, config)
+        }
+        AudioSource::Loopback => {
+            let device = host
+                .default_output_device()
+                .ok_or_else(|| audio_error("no default output device"))?;
+            let config = device.default_output_config().map_err(audio_error)?;
+            (device, config)
+        }
+        AudioSource::Device(name) => {
+            let device = host
+                .input_devices()
+                .map_err(audio_error)?
+                .find(|device| device.name().is_ok_and(|device_name| &device_name == name))
+                .ok_or_else(|| audio_error(format!("no input device named {}", name)))?;
+            let config = device.default_input_config().map_err(audio_error)?;
+            (device, config)
+        }
+    };
+
+    let stream = match config.sample_format() {
+        SampleFormat::F32 => build_stream::<f32>(&device, &config.config(), level),
+        SampleFormat::I16 => build_stream::<i16>(&device, &config.config(), level),
+        SampleFormat::U16 => build_stream::<u16>(&device, &config.config(), level),
+        other => return Err(audio_error(format!("unsupported sample format {}", other))),
+    }
+    .map_err(audio_error)?;
+    stream.play().map_err(audio_error)?;
+    Ok(stream)
+}
+
+/// Opens the stream on its own thread, since cpal streams can't move between threads.
+/// The stream lives until the returned sender is dropped.
+fn start_capture(source: AudioSource, level: Arc<AtomicU32>) -> Result<mpsc::Sender<()>, OverlayError> {
+    let (stop_tx, stop_rx) = mpsc::channel::<()>();
+    let (ready_tx, ready_rx) = mpsc::sync_channel(1);
+    std::thread::spawn(move || match open_stream(&source, level) {
+        // Kept alive until the binding drops the sender
+        Ok(_stream) => {
+            let _ = ready_tx.send(Ok(()));
+            let _ = stop_rx.recv();
+        }
+        Err(e) => {
+            let _ = ready_tx.send(Err(e));
+        }
+    });
+    ready_rx
+        .recv()
+        .unwrap_or_else(|_| Err(audio_error("capture thread exited")))?;
+    Ok(stop_tx)
+}
+
+/// A running binding; dropping it stops the capture and restores the configured look
+struct AudioBinding {
+    _timer: slint::Timer,
+    _capture: mpsc::Sender<()>,
+    window: Weak<OverlayUI>,
+    applied: Arc<Mutex<AppliedProps>>,
+    target: AudioTarget,
+    base_alpha: u8,
+    base_font_size: f32,
+}
+
+impl Drop for AudioBinding {
+    fn drop(&mut self) {
+        let Some(window) = self.window.upgrade() else {
+            return;
+        };
+        match self.target {
+            AudioTarget::Opacity { .. } => {
+                if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                    let _ = window_manager::set_window_transparency(hwnd, self.base_alpha);
+                }
+            }
+            AudioTarget::FontSize { .. } => lock_applied(&self.applied).set_font_size(&window, self.base_font_size),
+        }
+    }
+}
+
+impl OverlayManager {
+    /// Drives the overlay's opacity or font size with the level of an audio source. The
+    /// configured `window_alpha` and font size are the baseline, captured now and restored
+    /// on `unbind_audio_reactivity`; binding again replaces the previous binding.
+    pub fn bind_audio_reactivity(&self, overlay_id: &OverlayId, reactivity: AudioReactivity) -> Result<(), OverlayError> {
+        let (window_weak, applied, base_alpha, base_font_size) = {
+            let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+            let overlay = overlays
+                .get(overlay_id)
+                .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+            (
+                overlay.window_weak.clone(),
+                Arc::clone(&overlay.applied),
+                overlay.config.window_alpha,
+                overlay.config.text.font_size,
+            )
+        };
+
+        // Opening the device can take a moment, so it happens outside the overlays lock
+        let level = Arc::new(AtomicU32::new(0));
+        let capture = start_capture(reactivity.source.clone(), Arc::clone(&level))?;
+        let target = reactivity.target;
+        let mut follower = LevelFollower::new(reactivity.sensitivity, reactivity.smoothing);
+        let id = overlay_id.clone();
+
+        self.execute_ui_action(&window_weak, move |window| {
+            let weak = window.as_weak();
+            let tick_applied = Arc::clone(&applied);
+            let mut last_alpha = None;
+            let timer = slint::Timer::default();
+            timer.start(slint::TimerMode::Repeated, FRAME, move || {
+                let Some(window) = weak.upgrade() else {
+                    return;
+                };
+                let intensity = follower.update(f32::from_bits(level.load(Ordering::Relaxed)));
+                match target {
+                    AudioTarget::Opacity { min } => {
+                        let min = min.clamp(0.0, 1.0);
+                        let alpha = (base_alpha as f32 * (min + (1.0 - min) * intensity)).round() as u8;
+                        if last_alpha != Some(alpha) {
+                            last_alpha = Some(alpha);
+                            if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                                let _ = window_manager::set_window_transparency(hwnd, alpha);
+                            }
+                        }
+                    }
+                    AudioTarget::FontSize { max_scale } => {
+                        let scale = 1.0 + (max_scale.max(1.0) - 1.0) * intensity;
+                        lock_applied(&tick_applied).set_font_size(&window, base_font_size * scale);
+                    }
+                }
+            });
+
+            let binding = AudioBinding {
+                _timer: timer,
+                _capture: capture,
+                window: window.as_weak(),
+                applied,
+                target,
+                base_alpha,
+                base_font_size,
+            };
+            tasks::register(&id, TaskKind::Audio, binding);
+        })
+    }
+
+    /// Stops following the audio level and restores the configured opacity and font size
+    pub fn unbind_audio_reactivity(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        let id = overlay_id.clone();
+        crate::invoke_on_ui_thread(move || tasks::cancel(&id, TaskKind::Audio))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_follower_smooths_and_clamps() {
+        let mut instant = LevelFollower::new(4.0, 0.0);
+        assert_eq!(instant.update(0.1), 0.4);
+        assert_eq!(instant.update(0.5), 1.0);
+        assert_eq!(instant.update(0.0), 0.0);
+
+        let mut smooth = LevelFollower::new(1.0, 0.5);
+        assert_eq!(smooth.update(1.0), 0.5);
+        assert_eq!(smooth.update(1.0), 0.75);
+        assert_eq!(smooth.update(0.0), 0.375);
+    }
+
+    #[test]
+    fn test_rms() {
+        assert_eq!(rms::<f32>(&[]), 0.0);
+        assert_eq!(rms(&[0.5f32, -0.5, 0.5, -0.5]), 0.5);
+    }
+}
@@ -0,0 +1,91 @@
+use std::sync::mpsc;
+
+use crate::{invoke_on_ui_thread, lock_applied, OverlayError, OverlayId, OverlayManager, UI_THREAD};
+
+/// Largest window `create_autosized` makes; longer text wraps, and text taller than this is clipped
+const MAX_SIZE: (i32, i32) = (800, 400);
+
+/// Smallest window holding text measuring `text` with `padding` on each side, in whole
+/// pixels rounded up so nothing is clipped, and at most `max`
+fn fit_box(text: (f32, f32), padding: f32, max: (i32, i32)) -> (i32, i32) {
+    let fit = |extent: f32, max: i32| ((extent + 2.0 * padding).ceil() as i32).clamp(1, max);
+    (fit(text.0, max.0), fit(text.1, max.1))
+}
+
+impl OverlayManager {
+    /// Creates and shows an overlay sized to its text, so one-off notifications don't need a
+    /// guessed width and height. Everything but the text, position, font size and color comes
+    /// from `set_defaults`. The window grows up to 800x400; a line wider than that wraps at
+    /// word boundaries (`wrap_text`) and text taller than that is clipped.
+    ///
+    /// Like `create_overlay`, called off the UI thread this blocks until the overlay exists.
+    pub fn create_autosized(
+        &self,
+        text: &str,
+        x: i32,
+        y: i32,
+        font_size: f32,
+        color: &str,
+    ) -> Result<OverlayId, OverlayError> {
+        if !UI_THREAD.is_current() {
+            let (sender, receiver) = mpsc::sync_channel(1);
+            let manager = self.clone();
+            let (text, color) = (text.to_string(), color.to_string());
+            invoke_on_ui_thread(move || {
+                let _ = sender.send(manager.create_autosized(&text, x, y, font_size, &color));
+            })?;
+            return receiver.recv().unwrap_or(Err(OverlayError::EventLoopNotRunning));
+        }
+
+        let mut config = self.quick_config(text, x, y)?;
+        config.text.font_size = font_size;
+        config.text.color = color.to_string();
+        (config.width, config.height) = MAX_SIZE;
+        config.wrap_text = false;
+        let overlay_id = self.create_overlay(config)?;
+
+        self.fit_to_text(&overlay_id)?;
+        self.show_overlay(&overlay_id)?;
+        Ok(overlay_id)
+    }
+
+    /// Shrinks a freshly created overlay to its measured text, wrapping it when a line is
+    /// wider than `MAX_SIZE` allows. Must run on the UI thread.
+    fn fit_to_text(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get_mut(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+        let Some(window) = overlay.window_weak.upgrade() else {
+            return Ok(());
+        };
+        let padding = overlay.config.padding;
+
+        let mut text = (window.get_text_extent_width(), window.get_text_extent_height());
+        let wrap = text.0 + 2.0 * padding > MAX_SIZE.0 as f32;
+        if wrap {
+            // Measure the wrapped height at the widest box allowed
+            lock_applied(&overlay.applied).set_size(&window, MAX_SIZE.0, MAX_SIZE.1);
+            window.set_wrap_text(true);
+            text.1 = window.get_text_extent_height();
+        }
+
+        (overlay.config.width, overlay.config.height) = fit_box(text, padding, MAX_SIZE);
+        overlay.config.wrap_text = wrap;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_box() {
+        // Rounded up, padding on both sides
+        assert_eq!(fit_box((120.3, 28.0), 4.0, MAX_SIZE), (129, 36));
+        // Clamped to the maximum on each axis
+        assert_eq!(fit_box((1500.0, 900.0), 4.0, MAX_SIZE), MAX_SIZE);
+        assert_eq!(fit_box((0.0, 0.0), 0.0, MAX_SIZE), (1, 1));
+    }
+}
@@ -0,0 +1,224 @@
+//! IRC chat relay, enabled with the `chat` feature.
+//!
+//! Connects anonymously (read-only), so it works with public Twitch channels
+//! (`irc.chat.twitch.tv:6667`) as well as regular IRC servers.
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::TcpStream;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::{OverlayError, OverlayId, OverlayManager};
+
+/// Number of chat lines kept on screen
+const MAX_LINES: usize = 8;
+/// Minimum time between overlay updates; messages arriving faster are batched
+const UPDATE_INTERVAL: Duration = Duration::from_millis(250);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+#[derive(Debug, PartialEq)]
+enum IrcEvent {
+    Ping(String),
+    Message { nick: String, text: String },
+}
+
+fn parse_line(line: &str) -> Option<IrcEvent> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    // Twitch prefixes lines with IRCv3 tags when requested; skip them
+    let line = match line.strip_prefix('@') {
+        Some(tagged) => tagged.split_once(' ')?.1,
+        None => line,
+    };
+
+    if let Some(token) = line.strip_prefix("PING ") {
+        return Some(IrcEvent::Ping(token.to_string()));
+    }
+
+    let rest = line.strip_prefix(':')?;
+    let (prefix, rest) = rest.split_once(' ')?;
+    let (command, rest) = rest.split_once(' ')?;
+    if command != "PRIVMSG" {
+        return None;
+    }
+
+    let (_target, text) = rest.split_once(" :")?;
+    let nick = prefix.split('!').next()?.to_string();
+    Some(IrcEvent::Message {
+        nick,
+        text: text.to_string(),
+    })
+}
+
+/// Rolling window of the most recent chat lines
+struct ChatLog {
+    lines: VecDeque<String>,
+}
+
+impl ChatLog {
+    fn new() -> Self {
+        Self {
+            lines: VecDeque::with_capacity(MAX_LINES),
+        }
+    }
+
+    fn push(&mut self, line: String) {
+        if self.lines.len() == MAX_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    fn render(&self) -> String {
+        self.lines.iter().cloned().collect::<Vec<_>>().join("\n")
+    }
+}
+
+fn connect(server: &str, channel: &str) -> std::io::Result<TcpStream> {
+    let mut stream = TcpStream::connect(server)?;
+    stream.set_read_timeout(Some(UPDATE_INTERVAL))?;
+
+    let nick = format!("justinfan{}", std::process::id() % 100_000);
+    write!(stream, "NICK {}\r\nUSER {} 0 * :{}\r\nJOIN {}\r\n", nick, nick, nick, channel)?;
+    Ok(stream)
+}
+
+/// Reads the connection, feeding messages into the overlay.
+/// Returns `Ok` once the overlay no longer exists and `Err` when the connection is lost.
+fn relay(manager: &OverlayManager, overlay_id: &OverlayId, stream: TcpStream, chat_log: &mut ChatLog) -> std::io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    // Bytes rather than a String: a read timeout can split a multi-byte character
+    let mut line = Vec::new();
+    let mut dirty = false;
+    let mut last_update = Instant::now() - UPDATE_INTERVAL;
+
+    loop {
+        // Checked on every pass, read timeouts included, so a quiet channel still lets go
+        if manager.ensure_exists(overlay_id).is_err() {
+            return Ok(());
+        }
+
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => return Err(ErrorKind::UnexpectedEof.into()),
+            Ok(_) => {
+                match parse_line(&String::from_utf8_lossy(&line)) {
+                    Some(IrcEvent::Ping(token)) => write!(writer, "PONG {}\r\n", token)?,
+                    Some(IrcEvent::Message { nick, text }) => {
+                        chat_log.push(format!("{}: {}", nick, text));
+                        dirty = true;
+                    }
+                    None => {}
+                }
+                line.clear();
+            }
+            // Whatever arrived before the timeout stays in `line` and the next read completes it
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(e) => return Err(e),
+        }
+
+        if dirty && last_update.elapsed() >= UPDATE_INTERVAL {
+            if let Err(e) = manager.update_text(overlay_id, &chat_log.render()) {
+                overlay_log!(warn, &overlay_id, "Could not update chat overlay {}: {}", overlay_id, e);
+            }
+            dirty = false;
+            last_update = Instant::now();
+        }
+    }
+}
+
+impl OverlayManager {
+    /// Relays messages from an IRC channel into the overlay as a rolling list of recent lines.
+    ///
+    /// `server` is a `host:port` address and `channel` includes the leading `#`.
+    /// The relay reconnects with exponential backoff and stops once the overlay is removed.
+    pub fn relay_irc(&self, overlay_id: &OverlayId, server: &str, channel: &str) -> Result<JoinHandle<()>, OverlayError> {
+        self.get_overlay_config(overlay_id)?;
+
+        let manager = self.clone();
+        let overlay_id = overlay_id.clone();
+        let server = server.to_string();
+        let channel = channel.to_lowercase();
+
+        let handle = std::thread::spawn(move || {
+            let mut chat_log = ChatLog::new();
+            let mut delay = Duration::from_secs(1);
+
+            loop {
+                match connect(&server, &channel) {
+                    Ok(stream) => {
+                        overlay_log!(
+                            info,
+                            &overlay_id,
+                            "Relaying {} from {} to overlay {}",
+                            channel,
+                            server,
+                            overlay_id
+                        );
+                        delay = Duration::from_secs(1);
+                        match relay(&manager, &overlay_id, stream, &mut chat_log) {
+                            Ok(()) => break,
+                            Err(e) => overlay_log!(warn, &overlay_id, "Chat connection to {} lost: {}", server, e),
+                        }
+                    }
+                    Err(e) => overlay_log!(warn, &overlay_id, "Could not connect to {}: {}", server, e),
+                }
+
+                if manager.get_overlay_config(&overlay_id).is_err() {
+                    break;
+                }
+                std::thread::sleep(delay);
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+            }
+
+            overlay_log!(info, &overlay_id, "Chat relay for overlay {} stopped", overlay_id);
+        });
+
+        Ok(handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_privmsg() {
+        assert_eq!(
+            parse_line(":alice!alice@alice.tmi.twitch.tv PRIVMSG #chan :hello there\r\n"),
+            Some(IrcEvent::Message {
+                nick: "alice".to_string(),
+                text: "hello there".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_tagged_privmsg() {
+        assert_eq!(
+            parse_line("@badges=;color=#FF0000 :bob!bob@bob PRIVMSG #chan :hi"),
+            Some(IrcEvent::Message {
+                nick: "bob".to_string(),
+                text: "hi".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_ping_and_other() {
+        assert_eq!(parse_line("PING :tmi.twitch.tv"), Some(IrcEvent::Ping(":tmi.twitch.tv".to_string())));
+        assert_eq!(parse_line(":tmi.twitch.tv 001 justinfan :Welcome"), None);
+    }
+
+    #[test]
+    fn test_chat_log_rolls() {
+        let mut chat_log = ChatLog::new();
+        for i in 0..MAX_LINES + 2 {
+            chat_log.push(format!("line {}", i));
+        }
+        let rendered = chat_log.render();
+        assert!(!rendered.contains("line 1\n"));
+        assert!(rendered.starts_with("line 2\n"));
+        assert!(rendered.ends_with(&format!("line {}", MAX_LINES + 1)));
+    }
+}
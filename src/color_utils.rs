@@ -75,10 +75,345 @@ pub fn hex_to_argb_u32(color: &str) -> u32 {
     }
 }
 
+/// Magenta muy visible, usado cuando ningún color configurado se puede interpretar
+pub const MISCONFIGURED_COLOR: u32 = 0xFFFF00FF;
+
+/// Colores con nombre admitidos (colores básicos de CSS)
+const NAMED_COLORS: [(&str, u32); 17] = [
+    ("black", 0xFF000000),
+    ("silver", 0xFFC0C0C0),
+    ("gray", 0xFF808080),
+    ("white", 0xFFFFFFFF),
+    ("maroon", 0xFF800000),
+    ("red", 0xFFFF0000),
+    ("purple", 0xFF800080),
+    ("fuchsia", 0xFFFF00FF),
+    ("green", 0xFF008000),
+    ("lime", 0xFF00FF00),
+    ("olive", 0xFF808000),
+    ("yellow", 0xFFFFFF00),
+    ("navy", 0xFF000080),
+    ("blue", 0xFF0000FF),
+    ("teal", 0xFF008080),
+    ("aqua", 0xFF00FFFF),
+    ("transparent", 0x00000000),
+];
+
+/// Interpreta un porcentaje (`50%`) como alpha 0-255, limitado a 0..100%
+fn parse_percent_alpha(value: &str) -> Option<u32> {
+    let percent: f32 = value.trim().strip_suffix('%')?.trim().parse().ok()?;
+    if percent.is_nan() {
+        return None;
+    }
+    Some((percent.clamp(0.0, 100.0) / 100.0 * 255.0).round() as u32)
+}
+
+/// Interpreta el canal alpha de `rgba()`: porcentaje o fracción 0..1
+fn parse_alpha(value: &str) -> Option<u32> {
+    if let Some(alpha) = parse_percent_alpha(value) {
+        return Some(alpha);
+    }
+    let fraction: f32 = value.trim().parse().ok()?;
+    if fraction.is_nan() {
+        return None;
+    }
+    Some((fraction.clamp(0.0, 1.0) * 255.0).round() as u32)
+}
+
+/// `rgb(r, g, b)` o `rgba(r, g, b, a)` con canales 0-255
+fn parse_rgb_function(color: &str) -> Option<u32> {
+    let (args, has_alpha) = if let Some(args) = color.strip_prefix("rgba(") {
+        (args, true)
+    } else {
+        (color.strip_prefix("rgb(")?, false)
+    };
+    let parts: Vec<&str> = args.strip_suffix(')')?.split(',').collect();
+    if parts.len() != if has_alpha { 4 } else { 3 } {
+        return None;
+    }
+
+    let mut argb = 0;
+    for part in &parts[..3] {
+        let channel: u8 = part.trim().parse().ok()?;
+        argb = argb << 8 | channel as u32;
+    }
+    let alpha = if has_alpha { parse_alpha(parts[3])? } else { 0xFF };
+    Some(alpha << 24 | argb)
+}
+
+fn parse_base_color(color: &str) -> Option<u32> {
+    if color.starts_with("rgb") {
+        return parse_rgb_function(color);
+    }
+    if let Some(&(_, argb)) = NAMED_COLORS.iter().find(|(name, _)| name.eq_ignore_ascii_case(color)) {
+        return Some(argb);
+    }
+    let hex = color.trim_start_matches('#').trim_start_matches("0x");
+    if !is_valid_color(color) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(hex_to_argb_u32(color))
+}
+
+/// Convierte un color a u32 ARGB, o `None` si no es un color válido.
+///
+/// Gramática: `<base> [ "/" <porcentaje>% ]`, donde `<base>` es hex (`#RGB`, `#ARGB`,
+/// `#RRGGBB`, `#AARRGGBB`, también con `0x`), `rgb(r, g, b)`, `rgba(r, g, b, a)` (con `a`
+/// como `50%` o `0.5`) o un nombre básico de CSS (`red`, `white`, ...). El porcentaje
+/// final sustituye el alpha y se limita a 0..100%: `#FF0000 / 25%` es `0x40FF0000`.
+pub fn parse_argb(color: &str) -> Option<u32> {
+    let (base, percent) = match color.split_once('/') {
+        Some((base, percent)) => (base.trim(), Some(percent)),
+        None => (color.trim(), None),
+    };
+    let argb = parse_base_color(base)?;
+    match percent {
+        Some(percent) => Some(parse_percent_alpha(percent)? << 24 | (argb & 0x00FFFFFF)),
+        None => Some(argb),
+    }
+}
+
+/// Resuelve el color principal, luego el de respaldo y, si ambos fallan, magenta,
+/// registrando cada paso de la cadena
+pub fn resolve_color(primary: &str, fallback: Option<&str>) -> u32 {
+    if let Some(argb) = parse_argb(primary) {
+        return argb;
+    }
+    log::warn!("Invalid color {:?}", primary);
+
+    if let Some(fallback) = fallback {
+        if let Some(argb) = parse_argb(fallback) {
+            log::warn!("Using fallback color {}", fallback);
+            return argb;
+        }
+        log::warn!("Invalid fallback color {:?}", fallback);
+    }
+    log::warn!("Using magenta so the misconfigured color is noticeable");
+    MISCONFIGURED_COLOR
+}
+
+/// Sintaxis de color que acepta `parse_argb`, para que los clientes no tengan que suponerlas
+pub fn supported_formats() -> Vec<&'static str> {
+    vec![
+        "#RGB",
+        "#ARGB",
+        "#RRGGBB",
+        "#AARRGGBB",
+        "0x...",
+        "rgb(r, g, b)",
+        "rgba(r, g, b, a)",
+        "named",
+        "<color> / NN%",
+    ]
+}
+
+/// Problema encontrado al interpretar un color, para mostrarlo al usuario
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ColorWarning {
+    /// El color no se pudo interpretar
+    Invalid { color: String },
+    /// Parece un nombre de color, pero no es uno de los nombres básicos de CSS admitidos
+    UnknownNamedColor { name: String },
+    /// El porcentaje de alpha estaba fuera de 0..100% y se limitó
+    AlphaClamped { percent: f32 },
+    /// El sufijo `/ NN%` sustituyó el alpha que ya traía el color
+    AlphaReplaced { color: String },
+    /// El color principal falló y se usó el de respaldo
+    UsedFallback { fallback: String },
+    /// Ningún color se pudo usar; se dibuja en magenta
+    Misconfigured,
+}
+
+/// Añade a `warnings` los problemas de un color ya interpretado (`parsed`)
+fn diagnose(color: &str, parsed: bool, warnings: &mut Vec<ColorWarning>) {
+    let (base, percent) = match color.split_once('/') {
+        Some((base, percent)) => (base.trim(), Some(percent)),
+        None => (color.trim(), None),
+    };
+
+    if !parsed {
+        if !base.is_empty() && base.chars().all(|c| c.is_ascii_alphabetic()) {
+            warnings.push(ColorWarning::UnknownNamedColor { name: base.to_string() });
+        } else {
+            warnings.push(ColorWarning::Invalid { color: color.to_string() });
+        }
+        return;
+    }
+
+    let Some(percent) = percent.and_then(|p| p.trim().strip_suffix('%')?.trim().parse::<f32>().ok()) else {
+        return;
+    };
+    if !(0.0..=100.0).contains(&percent) {
+        warnings.push(ColorWarning::AlphaClamped { percent });
+    }
+    let hex = base.trim_start_matches('#').trim_start_matches("0x");
+    let has_alpha = base.starts_with("rgba(")
+        || base.eq_ignore_ascii_case("transparent")
+        || (base.len() != hex.len() && matches!(hex.len(), 4 | 8));
+    if has_alpha {
+        warnings.push(ColorWarning::AlphaReplaced { color: base.to_string() });
+    }
+}
+
+/// Igual que `resolve_color`, pero devuelve además los problemas encontrados en la cadena
+/// (color inválido, nombre desconocido, alpha limitado o sustituido, uso del respaldo) para
+/// poder mostrarlos. `resolve_color` sigue siendo la versión ligera para rutas frecuentes.
+pub fn resolve_color_with_warnings(primary: &str, fallback: Option<&str>) -> (u32, Vec<ColorWarning>) {
+    let mut warnings = Vec::new();
+    let parsed = parse_argb(primary);
+    diagnose(primary, parsed.is_some(), &mut warnings);
+    if let Some(argb) = parsed {
+        return (argb, warnings);
+    }
+
+    if let Some(fallback) = fallback {
+        let parsed = parse_argb(fallback);
+        diagnose(fallback, parsed.is_some(), &mut warnings);
+        if let Some(argb) = parsed {
+            warnings.push(ColorWarning::UsedFallback { fallback: fallback.to_string() });
+            return (argb, warnings);
+        }
+    }
+    warnings.push(ColorWarning::Misconfigured);
+    (MISCONFIGURED_COLOR, warnings)
+}
+
+fn srgb_to_linear(channel: u32) -> f32 {
+    let c = channel as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> u32 {
+    let c = if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    };
+    (c.clamp(0.0, 1.0) * 255.0).round() as u32
+}
+
+/// Interpola entre dos colores ARGB con `t` en 0..1. Los canales RGB se mezclan en
+/// espacio lineal (evita el tono apagado a mitad de camino) y el alpha de forma directa
+pub fn lerp_argb(from: u32, to: u32, t: f32) -> u32 {
+    let t = t.clamp(0.0, 1.0);
+    let channel = |shift: u32| {
+        let (a, b) = (srgb_to_linear((from >> shift) & 0xFF), srgb_to_linear((to >> shift) & 0xFF));
+        linear_to_srgb(a + (b - a) * t) << shift
+    };
+    let (alpha_from, alpha_to) = ((from >> 24) as f32, (to >> 24) as f32);
+    let alpha = (alpha_from + (alpha_to - alpha_from) * t).round() as u32;
+    alpha << 24 | channel(16) | channel(8) | channel(0)
+}
+
+/// Paleta de colores de identidad, bien diferenciados entre sí
+pub const IDENTITY_PALETTE: [&str; 8] = [
+    "#FFE6194B", "#FF3CB44B", "#FFFFE119", "#FF4363D8",
+    "#FFF58231", "#FF911EB4", "#FF42D4F4", "#FFF032E6",
+];
+
+/// Devuelve el color de identidad para la posición `index`, rotando por la paleta
+pub fn identity_color(index: usize) -> &'static str {
+    IDENTITY_PALETTE[index % IDENTITY_PALETTE.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_identity_color_rotates() {
+        assert_eq!(identity_color(0), IDENTITY_PALETTE[0]);
+        assert_eq!(identity_color(3), IDENTITY_PALETTE[3]);
+        assert_eq!(identity_color(IDENTITY_PALETTE.len() + 1), IDENTITY_PALETTE[1]);
+        assert!(IDENTITY_PALETTE.iter().all(|color| is_valid_color(color)));
+    }
+
+    #[test]
+    fn test_parse_argb_grammar() {
+        assert_eq!(parse_argb("#FF0000"), Some(0xFFFF0000));
+        assert_eq!(parse_argb("#FF0000 / 25%"), Some(0x40FF0000));
+        assert_eq!(parse_argb("#80FF0000/100%"), Some(0xFFFF0000));
+        assert_eq!(parse_argb("red / 50%"), Some(0x80FF0000));
+        assert_eq!(parse_argb("White"), Some(0xFFFFFFFF));
+        assert_eq!(parse_argb("rgba(255,0,0,50%)"), Some(0x80FF0000));
+        assert_eq!(parse_argb("rgba(0, 128, 255, 0.25)"), Some(0x400080FF));
+        assert_eq!(parse_argb("rgb(1, 2, 3)"), Some(0xFF010203));
+
+        // Percentages are clamped to 0..100
+        assert_eq!(parse_argb("#FF0000 / 150%"), Some(0xFFFF0000));
+        assert_eq!(parse_argb("#FF0000 / -20%"), Some(0x00FF0000));
+
+        assert_eq!(parse_argb("#FF0000 / 50"), None);
+        assert_eq!(parse_argb("rgb(256, 0, 0)"), None);
+        assert_eq!(parse_argb("rgba(1, 2, 3)"), None);
+        assert_eq!(parse_argb("blurple"), None);
+    }
+
+    #[test]
+    fn test_supported_formats_match_parser() {
+        let examples = ["#F00", "#8F00", "#FF0000", "#80FF0000", "0xFF0000", "rgb(255, 0, 0)", "rgba(255, 0, 0, 0.5)", "red", "red / 50%"];
+        assert_eq!(supported_formats().len(), examples.len());
+        for example in examples {
+            assert!(parse_argb(example).is_some(), "{}", example);
+        }
+    }
+
+    #[test]
+    fn test_resolve_color_warnings() {
+        assert_eq!(resolve_color_with_warnings("#FF0000", None), (0xFFFF0000, vec![]));
+
+        let (argb, warnings) = resolve_color_with_warnings("#80FF0000 / 150%", None);
+        assert_eq!(argb, 0xFFFF0000);
+        assert_eq!(
+            warnings,
+            vec![
+                ColorWarning::AlphaClamped { percent: 150.0 },
+                ColorWarning::AlphaReplaced { color: "#80FF0000".to_string() },
+            ]
+        );
+
+        let (argb, warnings) = resolve_color_with_warnings("blurple", Some("#00FF00"));
+        assert_eq!(argb, 0xFF00FF00);
+        assert_eq!(
+            warnings,
+            vec![
+                ColorWarning::UnknownNamedColor { name: "blurple".to_string() },
+                ColorWarning::UsedFallback { fallback: "#00FF00".to_string() },
+            ]
+        );
+
+        let (argb, warnings) = resolve_color_with_warnings("#GG0000", None);
+        assert_eq!(argb, MISCONFIGURED_COLOR);
+        assert_eq!(
+            warnings,
+            vec![ColorWarning::Invalid { color: "#GG0000".to_string() }, ColorWarning::Misconfigured]
+        );
+    }
+
+    #[test]
+    fn test_lerp_argb() {
+        assert_eq!(lerp_argb(0xFFFFFFFF, 0xFFFF0000, 0.0), 0xFFFFFFFF);
+        assert_eq!(lerp_argb(0xFFFFFFFF, 0xFFFF0000, 1.0), 0xFFFF0000);
+        assert_eq!(lerp_argb(0xFFFFFFFF, 0xFFFF0000, 2.0), 0xFFFF0000);
+        // Halfway between black and white in linear light is sRGB 188, not 128
+        assert_eq!(lerp_argb(0xFF000000, 0xFFFFFFFF, 0.5), 0xFFBCBCBC);
+        // Alpha is interpolated too
+        assert_eq!(lerp_argb(0x00000000, 0xFF000000, 0.5) >> 24, 0x80);
+    }
+
+    #[test]
+    fn test_resolve_color_fallback_chain() {
+        assert_eq!(resolve_color("#FF0000", Some("#00FF00")), 0xFFFF0000);
+        assert_eq!(resolve_color("#GG0000", Some("#00FF00")), 0xFF00FF00);
+        assert_eq!(resolve_color("blurple", Some("#nope")), MISCONFIGURED_COLOR);
+        assert_eq!(resolve_color("", None), MISCONFIGURED_COLOR);
+    }
+
     #[test]
     fn test_color_conversion() {
         assert_eq!(to_slint_color_string("#CC000000"), "#000000");
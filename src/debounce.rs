@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Collapses rapid show/hide requests so only the last one within the window is applied
+#[derive(Debug, Default)]
+pub(crate) struct VisibilityDebounce {
+    pub(crate) window: Duration,
+    next_generation: u64,
+    pending: HashMap<String, (u64, bool)>,
+}
+
+impl VisibilityDebounce {
+    /// Records the requested visibility and returns the generation to settle later
+    pub(crate) fn request(&mut self, overlay_id: &str, visible: bool) -> u64 {
+        self.next_generation += 1;
+        self.pending
+            .insert(overlay_id.to_string(), (self.next_generation, visible));
+        self.next_generation
+    }
+
+    /// Returns the visibility to apply if `generation` is still the latest request
+    pub(crate) fn settle(&mut self, overlay_id: &str, generation: u64) -> Option<bool> {
+        match self.pending.get(overlay_id) {
+            Some(&(latest, visible)) if latest == generation => {
+                self.pending.remove(overlay_id);
+                Some(visible)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rapid_toggles_settle_to_last_state() {
+        let mut debounce = VisibilityDebounce::default();
+        let generations: Vec<u64> = [true, false, true, false]
+            .iter()
+            .map(|&visible| debounce.request("a", visible))
+            .collect();
+        let other = debounce.request("b", true);
+
+        let settled: Vec<Option<bool>> = generations
+            .iter()
+            .map(|&generation| debounce.settle("a", generation))
+            .collect();
+        assert_eq!(settled, vec![None, None, None, Some(false)]);
+        assert_eq!(debounce.settle("b", other), Some(true));
+
+        // Once applied, a stale timer has nothing left to settle
+        assert_eq!(debounce.settle("a", generations[3]), None);
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt::Write;
+
+use crate::{OverlayConfig, OverlayError, OverlayId, OverlayManager};
+
+/// Slint color literal for `0xAARRGGBB`; Slint puts alpha last (`#RRGGBBAA`)
+fn slint_color(argb: u32) -> String {
+    format!("#{:06X}{:02X}", argb & 0x00FF_FFFF, argb >> 24)
+}
+
+/// Slint string literal for `text`, with quotes, backslashes and control characters escaped
+fn slint_string(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(literal, "\\u{{{:x}}}", c as u32);
+            }
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// A standalone `.slint` component drawing `text` the way the overlay does
+fn slint_snippet(config: &OverlayConfig, text: &str, text_argb: u32, background_argb: Option<u32>) -> String {
+    let (width, height) = config.window_size();
+    let (x, y) = config.text.position;
+    let mut snippet = String::new();
+    let _ = writeln!(snippet, "// Exported from subs_overlay (best effort); the window sat at ({}, {}) on screen", x, y);
+    let _ = writeln!(snippet, "export component Overlay inherits Window {{");
+    let _ = writeln!(snippet, "    no-frame: true;");
+    let _ = writeln!(snippet, "    background: transparent;");
+    let _ = writeln!(snippet, "    width: {}px;", width);
+    let _ = writeln!(snippet, "    height: {}px;", height);
+    let _ = writeln!(snippet);
+    let _ = writeln!(snippet, "    Rectangle {{");
+    if let Some(background) = background_argb {
+        let _ = writeln!(snippet, "        background: {};", slint_color(background));
+    }
+    let _ = writeln!(snippet, "        border-radius: 5px;");
+    let _ = writeln!(snippet);
+    let _ = writeln!(snippet, "        Text {{");
+    let _ = writeln!(snippet, "            x: {}px;", config.padding);
+    let _ = writeln!(snippet, "            y: {}px;", config.padding);
+    let _ = writeln!(snippet, "            width: parent.width - {}px;", 2.0 * config.padding);
+    let _ = writeln!(snippet, "            height: parent.height - {}px;", 2.0 * config.padding);
+    let _ = writeln!(snippet, "            text: {};", slint_string(text));
+    let _ = writeln!(snippet, "            font-size: {}px;", config.text.font_size);
+    let _ = writeln!(snippet, "            color: {};", slint_color(text_argb));
+    let _ = writeln!(snippet, "            horizontal-alignment: center;");
+    let _ = writeln!(snippet, "            vertical-alignment: center;");
+    if config.rotation_deg != 0.0 {
+        let _ = writeln!(snippet, "            transform-rotation: {}deg;", config.rotation_deg);
+    }
+    let _ = writeln!(snippet, "        }}");
+    let _ = writeln!(snippet, "    }}");
+    let _ = writeln!(snippet, "}}");
+    snippet
+}
+
+impl OverlayManager {
+    /// A `.slint` snippet reproducing the overlay's text, colors, font size, size and
+    /// rotation, as a starting point for a hand-written UI. This is a best-effort export:
+    /// colors are the resolved ones (named and functional colors become `#RRGGBBAA`), the
+    /// screen position is only noted in a comment, and effects such as the drop shadow,
+    /// karaoke or window alpha are left out.
+    pub fn export_slint(&self, overlay_id: &OverlayId) -> Result<String, OverlayError> {
+        let config = self.get_overlay_config(overlay_id)?;
+        let colors = self.rendered_colors(overlay_id)?;
+        Ok(slint_snippet(&config, &config.text.content, colors.text, colors.background))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slint_snippet() {
+        assert_eq!(slint_color(0x80FF0000), "#FF000080");
+        assert_eq!(slint_color(0xFF0A0B0C), "#0A0B0CFF");
+        assert_eq!(slint_string("say \"hi\"\\\nnow\t"), r#""say \"hi\"\\\nnow\u{9}""#);
+
+        let mut config = OverlayConfig::default();
+        config.text.font_size = 30.0;
+        config.rotation_deg = 45.0;
+        let snippet = slint_snippet(&config, "Hola", 0xFFFFFFFF, Some(0x80FF0000));
+        assert!(snippet.contains("text: \"Hola\";"));
+        assert!(snippet.contains("color: #FFFFFFFF;"));
+        assert!(snippet.contains("background: #FF000080;"));
+        assert!(snippet.contains("font-size: 30px;"));
+        assert!(snippet.contains("transform-rotation: 45deg;"));
+    }
+}
@@ -0,0 +1,141 @@
+//! "Click the text, pass through the gaps" for overlays.
+//!
+//! `HTTRANSPARENT` from `WM_NCHITTEST` only forwards clicks to windows of the same thread,
+//! so it can't hand them to the app underneath. Instead the cursor is polled and the
+//! window's `WS_EX_TRANSPARENT` style is switched on while it hovers a fully transparent
+//! gap and off while it hovers drawn content.
+
+use std::time::Duration;
+
+use slint::{ComponentHandle, Weak};
+
+use crate::tasks::{self, TaskKind};
+use crate::{window_manager, OverlayError, OverlayId, OverlayManager, OverlayUI};
+
+/// Interval between cursor checks
+const POLL: Duration = Duration::from_millis(30);
+
+/// Whether `point`, relative to the window's top-left corner, lands on drawn content in a
+/// window of size `window`. `text` is the size of the text, centered in the window, when
+/// only the text is drawn; `None` when the whole window is (an opaque box or an image).
+fn hits_content(point: (i32, i32), window: (i32, i32), text: Option<(f32, f32)>) -> bool {
+    let (x, y) = (point.0 as f32, point.1 as f32);
+    let (width, height) = (window.0 as f32, window.1 as f32);
+    if x < 0.0 || y < 0.0 || x >= width || y >= height {
+        return false;
+    }
+    let Some((text_width, text_height)) = text else {
+        return true;
+    };
+    let left = (width - text_width) / 2.0;
+    let top = (height - text_height) / 2.0;
+    x >= left && x < left + text_width && y >= top && y < top + text_height
+}
+
+/// A running hit test; dropping it puts the configured input passthrough back
+struct AlphaHitTest {
+    _timer: slint::Timer,
+    window: Weak<OverlayUI>,
+    manager: OverlayManager,
+    overlay_id: OverlayId,
+}
+
+impl Drop for AlphaHitTest {
+    fn drop(&mut self) {
+        let Some(window) = self.window.upgrade() else {
+            return;
+        };
+        // Read now rather than when enabled, `ignore_input` may have changed since
+        let Ok(config) = self.manager.get_overlay_config(&self.overlay_id) else {
+            return;
+        };
+        if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+            let _ = window_manager::set_input_passthrough(hwnd, config.ignore_input);
+        }
+    }
+}
+
+/// Whether the cursor is over drawn content of `window`, or `None` if that can't be told
+fn cursor_over_content(window: &OverlayUI) -> Option<bool> {
+    let hwnd = window_manager::get_native_handle(window.window()).ok()?;
+    let rect = window_manager::get_window_rect(hwnd).ok()?;
+    let (cursor_x, cursor_y) = window_manager::cursor_position().ok()?;
+
+    let fully_drawn = window.get_show_image() || window.get_box_background().color().alpha() > 0;
+    let scale = window.window().scale_factor();
+    let text = (!fully_drawn).then(|| {
+        (window.get_text_extent_width() * scale, window.get_text_extent_height() * scale)
+    });
+    Some(hits_content((cursor_x - rect.x, cursor_y - rect.y), (rect.width, rect.height), text))
+}
+
+impl OverlayManager {
+    /// Lets clicks through where the overlay is fully transparent while still catching
+    /// them over what it draws: the text, or the whole box when its background is visible
+    /// or it shows an image. Overrides `ignore_input` while enabled; disabling puts the
+    /// configured passthrough back. Only has an effect on Windows.
+    pub fn set_alpha_hit_test(&self, overlay_id: &OverlayId, enabled: bool) -> Result<(), OverlayError> {
+        let window_weak = {
+            let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+            let overlay = overlays
+                .get(overlay_id)
+                .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+            overlay.window_weak.clone()
+        };
+        let id = overlay_id.clone();
+        let manager = self.clone();
+
+        if !enabled {
+            return crate::invoke_on_ui_thread(move || tasks::cancel(&id, TaskKind::AlphaHitTest));
+        }
+
+        self.execute_ui_action(&window_weak, move |window| {
+            let weak = window.as_weak();
+            let mut passthrough = None;
+            let timer = slint::Timer::default();
+            timer.start(slint::TimerMode::Repeated, POLL, move || {
+                let Some(window) = weak.upgrade() else {
+                    return;
+                };
+                let Some(over_content) = cursor_over_content(&window) else {
+                    return;
+                };
+                // Only touch the window style when the answer changes
+                if passthrough != Some(!over_content) {
+                    passthrough = Some(!over_content);
+                    if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                        let _ = window_manager::set_input_passthrough(hwnd, !over_content);
+                    }
+                }
+            });
+
+            let hit_test = AlphaHitTest {
+                _timer: timer,
+                window: window.as_weak(),
+                manager,
+                overlay_id: id.clone(),
+            };
+            tasks::register(&id, TaskKind::AlphaHitTest, hit_test);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hits_content() {
+        // Only the 100x20 text, centered in a 300x100 window, is drawn
+        let text = Some((100.0, 20.0));
+        assert!(hits_content((150, 50), (300, 100), text));
+        assert!(hits_content((100, 40), (300, 100), text));
+        assert!(!hits_content((99, 50), (300, 100), text));
+        assert!(!hits_content((150, 60), (300, 100), text));
+
+        // The whole window is drawn, but nothing outside it
+        assert!(hits_content((0, 0), (300, 100), None));
+        assert!(!hits_content((300, 50), (300, 100), None));
+        assert!(!hits_content((-1, 50), (300, 100), None));
+    }
+}
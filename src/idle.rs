@@ -0,0 +1,130 @@
+//! Idle-aware opacity for overlays.
+//!
+//! A timer polls how long the user has been idle (`GetLastInputInfo`) and eases the
+//! window's layered alpha between an active and an idle value. Unbinding puts the
+//! overlay's `window_alpha` back, as it is configured at that moment.
+
+use std::time::Duration;
+
+use slint::{ComponentHandle, Weak};
+
+use crate::tasks::{self, TaskKind};
+use crate::{window_manager, OverlayError, OverlayId, OverlayManager, OverlayUI};
+
+/// How often the idle time is checked
+const POLL: Duration = Duration::from_millis(100);
+
+/// How long a fade between the two alphas takes
+const FADE: Duration = Duration::from_millis(600);
+
+/// Moves `current` towards `target` by at most `max_step`
+fn step_toward(current: f32, target: f32, max_step: f32) -> f32 {
+    if (target - current).abs() <= max_step {
+        target
+    } else {
+        current + max_step.copysign(target - current)
+    }
+}
+
+/// A running binding; dropping it puts the configured `window_alpha` back
+struct IdleOpacity {
+    _timer: slint::Timer,
+    window: Weak<OverlayUI>,
+    manager: OverlayManager,
+    overlay_id: OverlayId,
+}
+
+impl Drop for IdleOpacity {
+    fn drop(&mut self) {
+        let Some(window) = self.window.upgrade() else {
+            return;
+        };
+        // Read now rather than when bound, `window_alpha` may have changed since
+        let Ok(config) = self.manager.get_overlay_config(&self.overlay_id) else {
+            return;
+        };
+        if config.transparent || config.window_alpha != window_manager::PER_PIXEL_ALPHA {
+            if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                let _ = window_manager::set_window_transparency(hwnd, config.window_alpha);
+            }
+        }
+    }
+}
+
+impl OverlayManager {
+    /// Fades the overlay to `idle_alpha` once there has been no keyboard or mouse input for
+    /// `idle_after`, and back to `active_alpha` as soon as the user is active again, e.g. a
+    /// HUD that dims while you work and brightens when you step away. Binding again
+    /// replaces the previous binding. Only has an effect on Windows.
+    pub fn bind_idle_opacity(
+        &self,
+        overlay_id: &OverlayId,
+        active_alpha: u8,
+        idle_alpha: u8,
+        idle_after: Duration,
+    ) -> Result<(), OverlayError> {
+        let window_weak = {
+            let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+            let overlay = overlays
+                .get(overlay_id)
+                .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+            overlay.window_weak.clone()
+        };
+        let id = overlay_id.clone();
+        let manager = self.clone();
+        let max_step = (active_alpha.abs_diff(idle_alpha) as f32 * POLL.as_secs_f32() / FADE.as_secs_f32()).max(1.0);
+
+        self.execute_ui_action(&window_weak, move |window| {
+            let weak = window.as_weak();
+            let mut alpha = active_alpha as f32;
+            let mut last_applied = None;
+            let timer = slint::Timer::default();
+            timer.start(slint::TimerMode::Repeated, POLL, move || {
+                let Some(window) = weak.upgrade() else {
+                    return;
+                };
+                let Ok(idle) = window_manager::idle_time() else {
+                    return;
+                };
+                let target = if idle >= idle_after { idle_alpha } else { active_alpha };
+                alpha = step_toward(alpha, target as f32, max_step);
+
+                let rounded = alpha.round() as u8;
+                if last_applied != Some(rounded) {
+                    last_applied = Some(rounded);
+                    if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                        let _ = window_manager::set_window_transparency(hwnd, rounded);
+                    }
+                }
+            });
+
+            let binding = IdleOpacity {
+                _timer: timer,
+                window: window.as_weak(),
+                manager,
+                overlay_id: id.clone(),
+            };
+            tasks::register(&id, TaskKind::IdleOpacity, binding);
+        })
+    }
+
+    /// Stops following the idle state and restores the configured `window_alpha`
+    pub fn unbind_idle_opacity(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        let id = overlay_id.clone();
+        crate::invoke_on_ui_thread(move || tasks::cancel(&id, TaskKind::IdleOpacity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_toward() {
+        assert_eq!(step_toward(255.0, 80.0, 30.0), 225.0);
+        assert_eq!(step_toward(80.0, 255.0, 30.0), 110.0);
+        // The last step lands exactly on the target
+        assert_eq!(step_toward(90.0, 80.0, 30.0), 80.0);
+        assert_eq!(step_toward(80.0, 80.0, 30.0), 80.0);
+    }
+}
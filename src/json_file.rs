@@ -0,0 +1,53 @@
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::OverlayError;
+
+fn invalid_data(error: serde_json::Error) -> OverlayError {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, error).into()
+}
+
+/// Reads a value saved with `store_json`; a missing file (first run) gives the default
+pub(crate) fn load_json<T: DeserializeOwned + Default>(path: &Path) -> Result<T, OverlayError> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str(&json).map_err(invalid_data),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Writes `value` to `path` as pretty-printed JSON, replacing the file. The JSON goes to a
+/// temporary file that is then renamed over `path`, so a crash mid-write leaves the old file.
+pub(crate) fn store_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<(), OverlayError> {
+    let json = serde_json::to_string_pretty(value).map_err(invalid_data)?;
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    std::fs::write(&temp, json)?;
+    std::fs::rename(&temp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_json_replaces_file() {
+        let path = std::env::temp_dir().join(format!("subs_overlay_json_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(load_json::<Vec<u32>>(&path).unwrap(), Vec::<u32>::new());
+        store_json(&path, &vec![1, 2]).unwrap();
+        store_json(&path, &vec![3]).unwrap();
+        assert_eq!(load_json::<Vec<u32>>(&path).unwrap(), vec![3]);
+
+        // Nothing is left behind next to the file
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        assert!(!Path::new(&temp).exists());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
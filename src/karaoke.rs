@@ -0,0 +1,97 @@
+use std::time::{Duration, Instant};
+
+use slint::{ComponentHandle, ModelRc, SharedString, VecModel};
+
+use crate::tasks::{self, TaskKind};
+use crate::{OverlayError, OverlayId, OverlayManager};
+
+/// How often the highlight is re-evaluated
+const TICK: Duration = Duration::from_millis(20);
+
+/// Stops the karaoke timer of an overlay. Must run on the UI thread.
+pub(crate) fn cancel_karaoke(overlay_id: &OverlayId) {
+    tasks::cancel(overlay_id, TaskKind::Karaoke);
+}
+
+/// Index of the last word whose timestamp has passed, or -1 before the first one
+fn highlighted_word(timestamps: &[Duration], elapsed: Duration) -> i32 {
+    timestamps.iter().take_while(|&&at| at <= elapsed).count() as i32 - 1
+}
+
+impl OverlayManager {
+    /// Shows the line made of `words` and highlights each word once its timestamp, measured
+    /// from the start of playback, has passed. The line stays lit after the last word; any
+    /// later `update_text` or `stop_karaoke` switches back to plain text.
+    pub fn play_karaoke(&self, overlay_id: &OverlayId, words: Vec<(String, Duration)>) -> Result<(), OverlayError> {
+        let line = words.iter().map(|(word, _)| word.as_str()).collect::<Vec<_>>().join(" ");
+        self.update_text(overlay_id, &line)?;
+
+        let window_weak = {
+            let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+            overlays
+                .get(overlay_id)
+                .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?
+                .window_weak
+                .clone()
+        };
+        let (texts, timestamps): (Vec<SharedString>, Vec<Duration>) =
+            words.into_iter().map(|(word, at)| (SharedString::from(word), at)).unzip();
+        let id = overlay_id.clone();
+
+        self.execute_ui_action(&window_weak, move |window| {
+            window.set_karaoke_words(ModelRc::new(VecModel::from(texts)));
+            window.set_karaoke_index(-1);
+            window.set_karaoke_active(true);
+
+            let started = Instant::now();
+            let weak = window.as_weak();
+            let tick_id = id.clone();
+            let timer = slint::Timer::default();
+            timer.start(slint::TimerMode::Repeated, TICK, move || {
+                let Some(window) = weak.upgrade() else {
+                    return;
+                };
+                let index = highlighted_word(&timestamps, started.elapsed());
+                if index != window.get_karaoke_index() {
+                    window.set_karaoke_index(index);
+                }
+                if index + 1 >= timestamps.len() as i32 {
+                    // Every word is lit; keep the line on screen but stop ticking
+                    tasks::with_task(&tick_id, TaskKind::Karaoke, slint::Timer::stop);
+                }
+            });
+
+            tasks::register(&id, TaskKind::Karaoke, timer);
+        })
+    }
+
+    /// Ends karaoke highlighting and shows the overlay's plain text again
+    pub fn stop_karaoke(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+
+        let id = overlay_id.clone();
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            cancel_karaoke(&id);
+            window.set_karaoke_active(false);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlighted_word() {
+        let timestamps = [Duration::from_millis(0), Duration::from_millis(400), Duration::from_millis(900)];
+        let at = |ms| highlighted_word(&timestamps, Duration::from_millis(ms));
+        assert_eq!(highlighted_word(&timestamps[1..], Duration::ZERO), -1);
+        assert_eq!(at(0), 0);
+        assert_eq!(at(399), 0);
+        assert_eq!(at(400), 1);
+        assert_eq!(at(5000), 2);
+    }
+}
@@ -1,11 +1,48 @@
 use once_cell::sync::Lazy;
 use slint::{ComponentHandle, Weak, EventLoopError, PlatformError};
+use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::thread::ThreadId;
+use std::time::Duration;
+use unicode_segmentation::UnicodeSegmentation;
 use uuid::Uuid;
+use windows::Win32::Foundation::HWND;
 use log;
+use debounce::VisibilityDebounce;
+use links::OverlayLinks;
+use tasks::TaskKind;
+#[macro_use]
+mod logging;
+mod animation;
+mod autosize;
+#[cfg(feature = "audio")]
+pub mod audio;
 mod color_utils;
+mod debounce;
+mod export;
+mod hit_test;
+mod idle;
+mod json_file;
+mod karaoke;
+mod lines;
+mod links;
+mod metrics;
+mod oneshot;
+mod playlist;
+mod position_memory;
+mod presets;
+mod schedule;
+mod tasks;
+#[cfg(feature = "chat")]
+pub mod chat;
+#[cfg(feature = "osc")]
+pub mod osc;
+mod timer;
 pub mod window_manager;
 use thiserror::Error;
 
@@ -29,10 +66,44 @@ pub enum OverlayError {
     InvalidColor(String),
     #[error("Lock acquisition failed")]
     LockError,
+    #[error("Slint event loop is not running")]
+    EventLoopNotRunning,
+    #[error("Overlay is locked: {0}")]
+    Locked(String),
+    #[error("Could not load image: {0}")]
+    ImageLoad(String),
+    #[error("Invalid scale factor: {0} (must be greater than 0)")]
+    InvalidScaleFactor(f32),
+    #[cfg(feature = "audio")]
+    #[error("Audio capture error: {0}")]
+    Audio(String),
+    #[error("Overlay ignores input and can't take focus: {0}")]
+    NotInteractive(String),
+    #[error("Preset not found: {0}")]
+    PresetNotFound(String),
+    #[error("Linking {0} to {1} would create a cycle")]
+    LinkCycle(String, String),
+    #[error("Overlay limit reached ({0} overlays)")]
+    LimitExceeded(usize),
+    #[error("Clipboard error: {0}")]
+    Clipboard(String),
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+    #[error("I/O error: {source}")]
+    IoError {
+        #[from]
+        source: std::io::Error,
+    },
 }
 
 slint::include_modules!();
 
+pub use color_utils::{resolve_color_with_warnings, supported_formats as supported_color_formats, ColorWarning};
+pub use lines::{LineAlignment, StyledLine};
+pub use metrics::Metrics;
+pub use timer::format_clock;
+use metrics::MetricsCounters;
+
 pub type OverlayId = String;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -41,6 +112,13 @@ pub struct TextConfig {
     pub font_size: f32,
     pub color: String,
     pub position: (i32, i32),
+    /// Draws a dark copy of the text behind it, a cheap way to keep light text readable
+    /// over light backgrounds
+    #[serde(default)]
+    pub drop_shadow: bool,
+    /// Offset of the shadow from the text, in logical pixels
+    #[serde(default = "default_shadow_offset")]
+    pub shadow_offset: (f32, f32),
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -51,187 +129,2119 @@ pub struct OverlayConfig {
     pub transparent: bool,
     pub always_on_top: bool,
     pub ignore_input: bool,
+    /// Rejects moves, config changes and removal while set
+    #[serde(default)]
+    pub locked: bool,
+    /// Rounds the text position to whole pixels, which keeps small fonts crisp
+    #[serde(default)]
+    pub pixel_snap: bool,
+    /// Path of an image drawn behind the text, scaled to fit the window
+    #[serde(default)]
+    pub image: Option<String>,
+    /// Hides the window while the text is empty and shows it again on the next non-empty update
+    #[serde(default)]
+    pub hide_when_empty: bool,
+    /// Moves the window onto the nearest monitor when shown partly or fully off-screen
+    #[serde(default)]
+    pub clamp_to_screen: bool,
+    /// Uniform opacity of the whole window (255 = no dimming), applied on top of per-pixel alpha
+    #[serde(default = "default_window_alpha")]
+    pub window_alpha: u8,
+    /// Window title shown to capture tools; defaults to `subs-overlay-<short id>`
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Longest text shown, in characters; longer text is cut and ends with "…". `None` is unbounded
+    #[serde(default)]
+    pub max_chars: Option<usize>,
+    /// Color used when `text.color` can't be parsed; if both fail the text is drawn magenta
+    #[serde(default)]
+    pub fallback_color: Option<String>,
+    /// Stable name under which the position is remembered across restarts (see `enable_position_memory`)
+    #[serde(default)]
+    pub position_key: Option<String>,
+    /// Solid window background used instead of a black box when transparency isn't available
+    /// (remote desktop, some VMs); defaults to `OverlayConfig::DEFAULT_OPAQUE_BACKGROUND`
+    #[serde(default)]
+    pub opaque_background: Option<String>,
+    /// Clockwise rotation of the text around the box center, in degrees. The window grows
+    /// to the rotated bounding box so the text isn't clipped (see `OverlayConfig::window_size`)
+    #[serde(default)]
+    pub rotation_deg: f32,
+    /// Space between the text and the edges of its background box, in logical pixels
+    #[serde(default = "default_padding")]
+    pub padding: f32,
+    /// Stacking among always-on-top overlays: higher priorities stay above lower ones,
+    /// equal priorities stack in creation order
+    #[serde(default)]
+    pub priority: i32,
+    /// Renders text at the monitor's full DPI (default). Turning it off renders at one device
+    /// pixel per logical pixel, which on scaled displays draws far fewer pixels (a quarter at
+    /// 200%) at the cost of softer text; the window also shows at its unscaled pixel size
+    #[serde(default = "default_high_quality_text")]
+    pub high_quality_text: bool,
+    /// Path of a card background drawn over the box color and behind the text; transparent
+    /// parts of a PNG let the box color show through. If it can't be loaded the box color
+    /// is used alone
+    #[serde(default)]
+    pub background_image: Option<String>,
+    /// How `background_image` is scaled into the box
+    #[serde(default)]
+    pub background_fit: BackgroundFit,
+    /// Breaks lines wider than the box at word boundaries instead of letting them run past it
+    #[serde(default)]
+    pub wrap_text: bool,
+    /// Lines drawn stacked instead of `text.content`, each with its own style; see `set_lines`
+    #[serde(default)]
+    pub lines: Vec<StyledLine>,
+}
+
+/// How `OverlayConfig::background_image` fills the box
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundFit {
+    /// Fills the box, cropping what doesn't fit
+    #[default]
+    Cover,
+    /// Shows the whole image, leaving empty bands where the aspect ratios differ
+    Contain,
+    /// Stretches the image to the box
+    Fill,
+}
+
+impl BackgroundFit {
+    /// Value of the component's `background-fit-mode` property
+    fn slint_mode(self) -> i32 {
+        match self {
+            BackgroundFit::Cover => 0,
+            BackgroundFit::Contain => 1,
+            BackgroundFit::Fill => 2,
+        }
+    }
+}
+
+fn default_window_alpha() -> u8 {
+    window_manager::PER_PIXEL_ALPHA
+}
+
+fn default_shadow_offset() -> (f32, f32) {
+    (2.0, 2.0)
+}
+
+fn default_padding() -> f32 {
+    4.0
+}
+
+fn default_high_quality_text() -> bool {
+    true
+}
+
+impl Default for TextConfig {
+    fn default() -> Self {
+        Self {
+            content: String::new(),
+            font_size: 24.0,
+            color: "#FFFFFFFF".to_string(),
+            position: (0, 0),
+            drop_shadow: false,
+            shadow_offset: default_shadow_offset(),
+        }
+    }
+}
+
+impl Default for OverlayConfig {
+    fn default() -> Self {
+        Self {
+            text: TextConfig::default(),
+            width: 300,
+            height: 100,
+            transparent: true,
+            always_on_top: true,
+            ignore_input: true,
+            locked: false,
+            pixel_snap: false,
+            image: None,
+            hide_when_empty: false,
+            clamp_to_screen: false,
+            window_alpha: default_window_alpha(),
+            title: None,
+            max_chars: None,
+            fallback_color: None,
+            position_key: None,
+            opaque_background: None,
+            rotation_deg: 0.0,
+            padding: default_padding(),
+            priority: 0,
+            high_quality_text: true,
+            background_image: None,
+            background_fit: BackgroundFit::Cover,
+            wrap_text: false,
+            lines: Vec::new(),
+        }
+    }
+}
+
+impl TextConfig {
+    pub const MIN_FONT_SIZE: f32 = 1.0;
+    pub const MAX_FONT_SIZE: f32 = 512.0;
+    const FALLBACK_FONT_SIZE: f32 = 24.0;
+}
+
+/// Clamps a font size to the range the UI can lay out, logging when it had to adjust it
+pub fn clamp_font_size(font_size: f32) -> f32 {
+    let clamped = if font_size.is_nan() {
+        TextConfig::FALLBACK_FONT_SIZE
+    } else {
+        font_size.clamp(TextConfig::MIN_FONT_SIZE, TextConfig::MAX_FONT_SIZE)
+    };
+    if clamped != font_size {
+        log::warn!("Font size {} out of range, clamped to {}", font_size, clamped);
+    }
+    clamped
+}
+
+/// Cuts `text` to at most `max_chars` user-perceived characters (grapheme clusters),
+/// replacing the tail with "…" so truncation is visible
+pub fn truncate_text(text: &str, max_chars: usize) -> Cow<'_, str> {
+    let mut graphemes = text.grapheme_indices(true);
+    match graphemes.nth(max_chars) {
+        None => Cow::Borrowed(text),
+        Some(_) if max_chars == 0 => Cow::Borrowed(""),
+        Some(_) => {
+            let (cut, _) = text.grapheme_indices(true).nth(max_chars - 1).unwrap_or((0, ""));
+            Cow::Owned(format!("{}…", &text[..cut]))
+        }
+    }
+}
+
+/// Like `truncate_text`, but keeps the end of `text` and marks the cut with a leading "…"
+fn truncate_text_front(text: &str, max_chars: usize) -> Cow<'_, str> {
+    let count = text.graphemes(true).count();
+    match count.checked_sub(max_chars) {
+        None | Some(0) => Cow::Borrowed(text),
+        Some(_) if max_chars == 0 => Cow::Borrowed(""),
+        Some(excess) => {
+            let (cut, _) = text.grapheme_indices(true).nth(excess + 1).unwrap_or((text.len(), ""));
+            Cow::Owned(format!("…{}", &text[cut..]))
+        }
+    }
+}
+
+/// Orders `(priority, creation sequence, item)` entries bottom to top
+fn stacking_order<T>(mut entries: Vec<(i32, u64, T)>) -> Vec<T> {
+    entries.sort_by_key(|&(priority, sequence, _)| (priority, sequence));
+    entries.into_iter().map(|(_, _, item)| item).collect()
+}
+
+/// Maps a rotation to `0..360` degrees; non-finite values mean no rotation
+fn normalize_rotation(degrees: f32) -> f32 {
+    if degrees.is_finite() {
+        degrees.rem_euclid(360.0)
+    } else {
+        0.0
+    }
+}
+
+impl OverlayConfig {
+    pub const DEFAULT_OPAQUE_BACKGROUND: &'static str = "#FF202020";
+
+    /// Text color as `0xAARRGGBB`, resolved through `fallback_color` when `text.color` is invalid
+    pub fn text_color_argb(&self) -> u32 {
+        color_utils::resolve_color(&self.text.color, self.fallback_color.as_deref())
+    }
+
+    /// Like `text_color_argb`, also returning what went wrong along the way so it can be shown to the user
+    pub fn text_color_with_warnings(&self) -> (u32, Vec<ColorWarning>) {
+        color_utils::resolve_color_with_warnings(&self.text.color, self.fallback_color.as_deref())
+    }
+
+    /// Window size needed to show the box rotated by `rotation_deg` without clipping: the
+    /// configured size when unrotated, otherwise the rotated box's bounding box (rounded up)
+    pub fn window_size(&self) -> (i32, i32) {
+        if self.rotation_deg == 0.0 {
+            return (self.width, self.height);
+        }
+        let (sin, cos) = self.rotation_deg.to_radians().sin_cos();
+        let (width, height) = (self.width as f32, self.height as f32);
+        // Round away float noise first, so 90° gives exactly the swapped size
+        let bound = |value: f32| ((value * 1000.0).round() / 1000.0).ceil() as i32;
+        (
+            bound(width * cos.abs() + height * sin.abs()),
+            bound(width * sin.abs() + height * cos.abs()),
+        )
+    }
+
+    /// Normalizes values that would break layout; shared by every entry point that accepts a config
+    pub fn validate(&mut self) {
+        self.text.font_size = clamp_font_size(self.text.font_size);
+        self.rotation_deg = normalize_rotation(self.rotation_deg);
+        self.padding = if self.padding.is_finite() { self.padding.max(0.0) } else { default_padding() };
+        if let Some(max_chars) = self.max_chars {
+            if let Cow::Owned(truncated) = truncate_text(&self.text.content, max_chars) {
+                self.text.content = truncated;
+            }
+        }
+    }
 }
 
+/// Everything a test harness needs to know about an overlay, from `OverlayManager::debug_dump`.
+///
+/// Live values are read from the window when it is reachable (on the UI thread); otherwise,
+/// e.g. in headless runs, they fall back to the stored config and the native-only fields are `None`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct OverlayDebugInfo {
+    pub id: OverlayId,
+    pub text: String,
+    /// Applied text color as `0xAARRGGBB`
+    pub color_argb: u32,
+    pub font_size: f32,
+    pub native_rect: Option<window_manager::ScreenRect>,
+    pub visible: Option<bool>,
+    pub topmost: bool,
+}
+
+/// One row of `OverlayManager::list_overlays_detailed`
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct OverlaySummary {
+    pub id: OverlayId,
+    /// Current text, cut to `OverlaySummary::TEXT_PREVIEW_CHARS` characters
+    pub text: String,
+    pub position: (i32, i32),
+    pub size: (i32, i32),
+    /// `None` when the window can't be inspected from the calling thread
+    pub visible: Option<bool>,
+}
+
+impl OverlaySummary {
+    pub const TEXT_PREVIEW_CHARS: usize = 40;
+}
+
+/// Colors an overlay is actually drawn with, as `0xAARRGGBB`; see `OverlayManager::rendered_colors`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct RenderedColors {
+    /// Text color after parsing, fallback and any running color animation
+    pub text: u32,
+    /// Fill of the box behind the text; `None` when the window can't be inspected from the calling thread
+    pub background: Option<u32>,
+}
+
+/// Change notification delivered to the observer set with `OverlayManager::set_observer`
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OverlayEvent {
+    Created { id: OverlayId, config: OverlayConfig },
+    TextUpdated { id: OverlayId, text: String },
+    Moved { id: OverlayId, x: i32, y: i32 },
+    ConfigUpdated { id: OverlayId, config: OverlayConfig },
+    Removed { id: OverlayId },
+}
+
+type Observer = Arc<dyn Fn(&OverlayEvent) + Send + Sync>;
+
+fn notify_observer(observer: &Mutex<Option<Observer>>, event: OverlayEvent) {
+    // Called with the lock released, so the observer can call back into the manager
+    let observer = observer.lock().ok().and_then(|observer| observer.clone());
+    if let Some(observer) = observer {
+        observer(&event);
+    }
+}
+
+/// Registry of overlay windows. Clones share the same overlays, so a clone can be
+/// handed to background listeners.
+#[derive(Clone)]
 pub struct OverlayManager {
     overlays: Arc<Mutex<HashMap<OverlayId, OverlayWindow>>>,
+    metrics: Arc<MetricsCounters>,
+    observer: Arc<Mutex<Option<Observer>>>,
+    visibility: Arc<Mutex<VisibilityDebounce>>,
+    snap_threshold: Arc<AtomicU32>,
+    /// Grid positions snap to, `0` for none
+    grid: Arc<AtomicU32>,
+    identity_colors: Arc<AtomicUsize>,
+    /// Saved settings while presentation mode is active, `None` otherwise
+    presentation: Arc<Mutex<Option<HashMap<OverlayId, PresentationBackup>>>>,
+    /// Template for `create_overlay_quick`
+    defaults: Arc<Mutex<OverlayConfig>>,
+    position_file: Arc<Mutex<PathBuf>>,
+    /// Remembered rects not yet written to `position_file`
+    pending_positions: Arc<position_memory::PendingPositions>,
+    /// Overlays to show again on unmute while muted, `None` otherwise
+    muted: Arc<Mutex<Option<HashSet<OverlayId>>>>,
+    links: Arc<Mutex<OverlayLinks>>,
+    /// Named styles for `apply_preset_to_all`
+    presets: Arc<Mutex<HashMap<String, OverlayConfig>>>,
+    /// Where presets are saved, `None` to keep them in memory only
+    preset_file: Arc<Mutex<Option<PathBuf>>>,
+    debug_borders: Arc<AtomicBool>,
+    next_sequence: Arc<AtomicU64>,
+    /// Most overlays `create_overlay` allows at once, `usize::MAX` for no limit
+    max_overlays: Arc<AtomicUsize>,
+}
+
+/// Per-overlay settings overridden by presentation mode, kept so leaving it is lossless
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PresentationBackup {
+    ignore_input: bool,
+    always_on_top: bool,
+    locked: bool,
+}
+
+impl PresentationBackup {
+    /// Saves the settings presentation mode overrides, then forces them on
+    fn force(config: &mut OverlayConfig) -> Self {
+        let backup = Self {
+            ignore_input: config.ignore_input,
+            always_on_top: config.always_on_top,
+            locked: config.locked,
+        };
+        config.ignore_input = true;
+        config.always_on_top = true;
+        config.locked = true;
+        backup
+    }
+
+    fn restore(self, config: &mut OverlayConfig) {
+        config.ignore_input = self.ignore_input;
+        config.always_on_top = self.always_on_top;
+        config.locked = self.locked;
+    }
 }
 
+/// Default distance, in pixels, at which dragged overlays snap to edges
+const DEFAULT_SNAP_THRESHOLD: u32 = 10;
+
 struct OverlayWindow {
     window_weak: Weak<OverlayUI>,
     config: OverlayConfig,
+    created_on: ThreadId,
+    /// Position in creation order, from `OverlayManager::next_sequence`
+    sequence: u64,
+    /// Values last pushed to the window, shared with the UI-thread closures that push them
+    applied: Arc<Mutex<AppliedProps>>,
+}
+
+/// Last values set on an overlay's Slint window, so unchanged ones aren't set again
+/// (each set can trigger a layout pass)
+#[derive(Debug, Default, Clone, PartialEq)]
+struct AppliedProps {
+    size: Option<(i32, i32)>,
+    font_size: Option<f32>,
+    text: Option<String>,
+    color: Option<u32>,
+}
+
+impl AppliedProps {
+    /// Records `value` and reports whether it differs from the previous one
+    fn changed<T: PartialEq>(slot: &mut Option<T>, value: T) -> bool {
+        if slot.as_ref() == Some(&value) {
+            return false;
+        }
+        *slot = Some(value);
+        true
+    }
+
+    fn set_size(&mut self, window: &OverlayUI, width: i32, height: i32) {
+        if Self::changed(&mut self.size, (width, height)) {
+            window.set_win_width(width as f32);
+            window.set_win_height(height as f32);
+        }
+    }
+
+    fn set_font_size(&mut self, window: &OverlayUI, font_size: f32) {
+        if Self::changed(&mut self.font_size, font_size) {
+            window.set_font_size(font_size);
+        }
+    }
+
+    fn set_text(&mut self, window: &OverlayUI, text: &str) {
+        if self.text.as_deref() != Some(text) {
+            self.text = Some(text.to_string());
+            window.set_text_content(text.into());
+        }
+    }
+
+    fn set_color(&mut self, window: &OverlayUI, argb: u32) {
+        if Self::changed(&mut self.color, argb) {
+            window.set_text_color(slint::Brush::from(slint::Color::from_argb_encoded(argb)));
+        }
+    }
+}
+
+/// Locks the applied-props tracker, recovering it if a panicking UI closure poisoned it
+fn lock_applied(applied: &Mutex<AppliedProps>) -> std::sync::MutexGuard<'_, AppliedProps> {
+    applied.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
 }
 
 thread_local! {
     static WINDOW_HOLDER: RefCell<HashMap<OverlayId, OverlayUI>> = RefCell::new(HashMap::new());
+    static WATCHDOG_TIMER: RefCell<Option<slint::Timer>> = const { RefCell::new(None) };
+    static TOPMOST_TIMER: RefCell<Option<slint::Timer>> = const { RefCell::new(None) };
+    /// Window to return the foreground to when a focused overlay is blurred
+    static FOCUS_RETURN: RefCell<HashMap<OverlayId, HWND>> = RefCell::new(HashMap::new());
+}
+
+/// Maps a missing or terminated event loop to `EventLoopNotRunning`
+fn event_loop_error(error: EventLoopError) -> OverlayError {
+    match error {
+        EventLoopError::EventLoopTerminated | EventLoopError::NoEventLoopProvider => OverlayError::EventLoopNotRunning,
+        other => OverlayError::from(other),
+    }
+}
+
+/// Safe-area inset in percent, stored as `f32` bits; applies to every manager
+static SAFE_AREA_INSET: AtomicU32 = AtomicU32::new(0);
+
+/// Connected monitors with their work areas shrunk to the configured safe area
+fn usable_monitors() -> Vec<window_manager::MonitorInfo> {
+    let inset = f32::from_bits(SAFE_AREA_INSET.load(Ordering::Relaxed));
+    let mut monitors = window_manager::enumerate_monitors();
+    for monitor in &mut monitors {
+        monitor.work_area = monitor.work_area.inset(inset);
+    }
+    monitors
+}
+
+/// The thread that claimed ownership of something; the first claim wins
+struct OwnerThread(OnceLock<ThreadId>);
+
+impl OwnerThread {
+    const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    /// Makes the calling thread the owner, unless another thread already is
+    fn claim(&self) {
+        let _ = self.0.set(std::thread::current().id());
+    }
+
+    /// Whether the calling thread is the owner; `false` while nobody has claimed it
+    fn is_current(&self) -> bool {
+        self.0.get() == Some(&std::thread::current().id())
+    }
+}
+
+/// Thread holding every overlay window in its `WINDOW_HOLDER`: the one running the Slint event
+/// loop, claimed by `init_ui_thread` or by the first closure the event loop runs for us. Windows
+/// requested from any other thread are created there, so removal always finds (and drops) them.
+static UI_THREAD: OwnerThread = OwnerThread::new();
+
+/// Marks the calling thread as the one that runs the Slint event loop. Call it there before
+/// creating overlays ahead of `slint::run_event_loop`; otherwise creation is sent to the
+/// event loop, which isn't running yet, and blocks.
+pub fn init_ui_thread() {
+    UI_THREAD.claim();
+}
+
+/// Schedules `action` on the UI thread
+pub(crate) fn invoke_on_ui_thread<F>(action: F) -> Result<(), OverlayError>
+where
+    F: FnOnce() + Send + 'static,
+{
+    slint::invoke_from_event_loop(move || {
+        UI_THREAD.claim();
+        action();
+    })
+    .map_err(event_loop_error)
+}
+
+/// Drops the UI-thread resources of a removed overlay. Must run on the UI thread.
+fn release_overlay_resources(overlay_id: &OverlayId) {
+    FOCUS_RETURN.with(|focus| {
+        focus.borrow_mut().remove(overlay_id);
+    });
+    tasks::cancel_overlay(overlay_id);
+    WINDOW_HOLDER.with(|holder| {
+        holder.borrow_mut().remove(overlay_id);
+    });
+}
+
+/// Whether text measuring `text` (logical pixels) fits a box of `box_size` with `padding` on each side
+fn text_fits_box(text: (f32, f32), box_size: (f32, f32), padding: f32) -> bool {
+    text.0 <= box_size.0 - 2.0 * padding && text.1 <= box_size.1 - 2.0 * padding
+}
+
+/// Parses an overlay configuration pasted from the clipboard
+fn config_from_json(json: &str) -> Result<OverlayConfig, OverlayError> {
+    serde_json::from_str(json.trim())
+        .map_err(|e| OverlayError::Clipboard(format!("clipboard does not hold an overlay config: {}", e)))
+}
+
+/// Shows `config.background_image`, or just the box color when there is none or it can't be loaded
+fn set_background_image(window: &OverlayUI, config: &OverlayConfig) {
+    window.set_background_fit_mode(config.background_fit.slint_mode());
+    let image = config.background_image.as_deref().and_then(|path| {
+        slint::Image::load_from_path(std::path::Path::new(path))
+            .inspect_err(|_| {
+                overlay_log!(
+                    warn,
+                    &window.get_debug_label(),
+                    "Could not load background image {}, using the box color",
+                    path
+                )
+            })
+            .ok()
+    });
+    match image {
+        Some(image) => {
+            window.set_background_image(image);
+            window.set_show_background_image(true);
+        }
+        None => window.set_show_background_image(false),
+    }
+}
+
+/// Renders at the monitor's DPI, or at scale 1.0 for `high_quality_text: false`
+fn set_render_quality(window: &OverlayUI, high_quality: bool) {
+    let scale_factor = if high_quality {
+        match window_manager::get_native_handle(window.window()).and_then(window_manager::window_scale_factor) {
+            Ok(scale_factor) => scale_factor,
+            Err(_) => return,
+        }
+    } else {
+        1.0
+    };
+    window
+        .window()
+        .dispatch_event(slint::platform::WindowEvent::ScaleFactorChanged { scale_factor });
+}
+
+fn set_drop_shadow(window: &OverlayUI, text: &TextConfig) {
+    window.set_drop_shadow(text.drop_shadow);
+    window.set_shadow_offset_x(text.shadow_offset.0);
+    window.set_shadow_offset_y(text.shadow_offset.1);
+}
+
+/// Sizes and shows a window, then applies the native window properties and position
+fn show_window(window: &OverlayUI, config: &OverlayConfig, applied: &Mutex<AppliedProps>) -> Result<(), OverlayError> {
+    {
+        let mut applied = lock_applied(applied);
+        let (width, height) = config.window_size();
+        applied.set_size(window, width, height);
+        applied.set_font_size(window, config.text.font_size);
+    }
+
+    window.show()?;
+    if !config.high_quality_text {
+        set_render_quality(window, false);
+    }
+
+    // Set window position and apply properties
+    if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+        let applied = window_manager::apply_window_properties(
+            hwnd,
+            config.transparent,
+            config.always_on_top,
+            config.ignore_input,
+        );
+        if config.transparent {
+            let failure = match applied {
+                Err(e) => Some(e.to_string()),
+                Ok(()) if !window_manager::composition_enabled() => Some("desktop composition is off".to_string()),
+                Ok(()) => None,
+            };
+            if let Some(reason) = failure {
+                let background = config.opaque_background.as_deref().unwrap_or(OverlayConfig::DEFAULT_OPAQUE_BACKGROUND);
+                overlay_log!(
+                    warn,
+                    &window.get_debug_label(),
+                    "Transparency unavailable ({}), using an opaque {} background",
+                    reason,
+                    background
+                );
+                let argb = color_utils::resolve_color(background, Some(OverlayConfig::DEFAULT_OPAQUE_BACKGROUND));
+                window.set_window_background(slint::Brush::from(slint::Color::from_argb_encoded(argb)));
+            }
+        }
+        if config.window_alpha != window_manager::PER_PIXEL_ALPHA {
+            let _ = window_manager::set_window_transparency(hwnd, config.window_alpha);
+        }
+        let (mut x, mut y) = config.text.position;
+        if config.clamp_to_screen {
+            let (width, height) = config.window_size();
+            let rect = window_manager::ScreenRect { x, y, width, height };
+            let clamped = window_manager::clamp_rect_to_monitors(rect, &usable_monitors());
+            if clamped != rect {
+                overlay_log!(
+                    warn,
+                    &window.get_debug_label(),
+                    "Overlay at ({}, {}) is off-screen, moved to ({}, {})",
+                    x,
+                    y,
+                    clamped.x,
+                    clamped.y
+                );
+                (x, y) = (clamped.x, clamped.y);
+            }
+        }
+        let _ = window_manager::set_window_position(hwnd, x, y);
+    }
+
+    Ok(())
+}
+
+/// Takes out the entries whose window has been destroyed and returns them.
+///
+/// Only entries created on the calling thread are inspected, since a Slint `Weak`
+/// can't be upgraded from any other thread.
+fn take_dead_overlays(overlays: &Mutex<HashMap<OverlayId, OverlayWindow>>) -> Result<Vec<(OverlayId, OverlayWindow)>, OverlayError> {
+    let mut overlays = overlays.lock().map_err(|_| OverlayError::LockError)?;
+    let current = std::thread::current().id();
+    let dead: Vec<OverlayId> = overlays
+        .iter()
+        .filter(|(_, overlay)| overlay.created_on == current && overlay.window_weak.upgrade().is_none())
+        .map(|(overlay_id, _)| overlay_id.clone())
+        .collect();
+    Ok(dead
+        .into_iter()
+        .filter_map(|overlay_id| overlays.remove(&overlay_id).map(|overlay| (overlay_id, overlay)))
+        .collect())
 }
 
 impl OverlayManager {
     pub fn new() -> Self {
         Self {
             overlays: Arc::new(Mutex::new(HashMap::new())),
+            metrics: Arc::new(MetricsCounters::default()),
+            observer: Arc::new(Mutex::new(None)),
+            visibility: Arc::new(Mutex::new(VisibilityDebounce::default())),
+            snap_threshold: Arc::new(AtomicU32::new(DEFAULT_SNAP_THRESHOLD)),
+            grid: Arc::new(AtomicU32::new(0)),
+            identity_colors: Arc::new(AtomicUsize::new(0)),
+            presentation: Arc::new(Mutex::new(None)),
+            defaults: Arc::new(Mutex::new(OverlayConfig::default())),
+            position_file: Arc::new(Mutex::new(PathBuf::from(position_memory::DEFAULT_POSITION_FILE))),
+            pending_positions: Arc::new(position_memory::PendingPositions::default()),
+            muted: Arc::new(Mutex::new(None)),
+            links: Arc::new(Mutex::new(OverlayLinks::default())),
+            presets: Arc::new(Mutex::new(HashMap::new())),
+            preset_file: Arc::new(Mutex::new(None)),
+            debug_borders: Arc::new(AtomicBool::new(false)),
+            next_sequence: Arc::new(AtomicU64::new(0)),
+            max_overlays: Arc::new(AtomicUsize::new(usize::MAX)),
+        }
+    }
+
+    /// Caps how many overlays can exist at once; past it `create_overlay` fails with
+    /// `LimitExceeded`. A guardrail against runaway creation, e.g. by a buggy client.
+    /// Unlimited by default; existing overlays are never removed by a lower limit.
+    pub fn set_max_overlays(&self, limit: usize) {
+        self.max_overlays.store(limit, Ordering::Relaxed);
+    }
+
+    /// Fails with `LimitExceeded` if creating one more overlay would go past the limit
+    fn check_overlay_limit(&self) -> Result<(), OverlayError> {
+        let limit = self.max_overlays.load(Ordering::Relaxed);
+        let count = self.overlays.lock().map_err(|_| OverlayError::LockError)?.len();
+        if count >= limit {
+            return Err(OverlayError::LimitExceeded(limit));
+        }
+        Ok(())
+    }
+
+    /// Creates an overlay window (hidden until `show_overlay`).
+    ///
+    /// Called off the UI thread, creation is routed through the event loop and this blocks
+    /// until the window exists, so the event loop must be running.
+    pub fn create_overlay(&self, config: OverlayConfig) -> Result<OverlayId, OverlayError> {
+        if !UI_THREAD.is_current() {
+            let (sender, receiver) = mpsc::sync_channel(1);
+            let manager = self.clone();
+            invoke_on_ui_thread(move || {
+                let _ = sender.send(manager.create_overlay(config));
+            })?;
+            return receiver.recv().unwrap_or(Err(OverlayError::EventLoopNotRunning));
+        }
+
+        // Creation only happens on the UI thread, so nothing can be added between this
+        // check and the insert below
+        self.check_overlay_limit()?;
+        if config.fallback_color.is_none() && color_utils::parse_argb(&config.text.color).is_none() {
+            return Err(OverlayError::InvalidColor(config.text.color.clone()));
+        }
+        let mut config = config;
+        self.restore_position(&mut config)?;
+        config.validate();
+
+        let image = match &config.image {
+            Some(path) => Some(
+                slint::Image::load_from_path(std::path::Path::new(path))
+                    .map_err(|_| OverlayError::ImageLoad(path.clone()))?,
+            ),
+            None => None,
+        };
+
+        let overlay_id = Uuid::new_v4().to_string();
+        if let Some(backups) = self.presentation.lock().map_err(|_| OverlayError::LockError)?.as_mut() {
+            backups.insert(overlay_id.clone(), PresentationBackup::force(&mut config));
+        }
+        if config.title.is_none() {
+            config.title = Some(format!("subs-overlay-{}", &overlay_id[..8]));
+        }
+
+        let ui = OverlayUI::new()?;
+
+        let mut applied = AppliedProps::default();
+        applied.set_text(&ui, &config.text.content);
+        applied.set_font_size(&ui, config.text.font_size);
+        applied.set_color(&ui, config.text_color_argb());
+        ui.set_pixel_snap(config.pixel_snap);
+        ui.set_rotation_deg(config.rotation_deg);
+        ui.set_box_padding(config.padding);
+        ui.set_wrap_text(config.wrap_text);
+        lines::apply_lines(&ui, &config);
+        set_drop_shadow(&ui, &config.text);
+        set_background_image(&ui, &config);
+        ui.set_window_title(config.title.clone().unwrap_or_default().into());
+        ui.set_debug_label(overlay_id.as_str().into());
+        ui.set_debug_borders(self.debug_borders.load(Ordering::Relaxed));
+        if let Some(image) = image {
+            ui.set_image_source(image);
+            ui.set_show_image(true);
+            ui.set_box_background(slint::Brush::from(slint::Color::from_argb_u8(0, 0, 0, 0)));
+        }
+
+        WINDOW_HOLDER.with(|holder| {
+            holder.borrow_mut().insert(overlay_id.clone(), ui.clone_strong());
+        });
+
+        let overlay_window = OverlayWindow {
+            window_weak: ui.as_weak(),
+            config: config.clone(),
+            created_on: std::thread::current().id(),
+            sequence: self.next_sequence.fetch_add(1, Ordering::Relaxed),
+            applied: Arc::new(Mutex::new(applied)),
+        };
+
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        overlays.insert(overlay_id.clone(), overlay_window);
+        drop(overlays);
+        MetricsCounters::incr(&self.metrics.overlays_created);
+        overlay_log!(debug, &overlay_id, "Created overlay {}", overlay_id);
+
+        self.notify(OverlayEvent::Created {
+            id: overlay_id.clone(),
+            config,
+        });
+
+        Ok(overlay_id)
+    }
+
+    /// Hides every overlay at once, remembering which ones were visible. Overlays shown while
+    /// muted stay hidden until `unmute`. Muting twice is a no-op.
+    pub fn mute(&self) -> Result<(), OverlayError> {
+        let manager = self.clone();
+        invoke_on_ui_thread(move || {
+            let Ok(mut muted) = manager.muted.lock() else {
+                return;
+            };
+            if muted.is_some() {
+                return;
+            }
+            let Ok(overlays) = manager.overlays.lock() else {
+                return;
+            };
+
+            let mut visible = HashSet::new();
+            for (overlay_id, overlay) in overlays.iter() {
+                if let Some(window) = overlay.window_weak.upgrade() {
+                    if window.window().is_visible() {
+                        if let Err(e) = window.hide() {
+                            overlay_log!(warn, overlay_id, "Could not hide overlay {} while muting: {}", overlay_id, e);
+                        }
+                        visible.insert(overlay_id.clone());
+                    }
+                }
+            }
+            *muted = Some(visible);
+        })
+    }
+
+    /// Shows again exactly the overlays that were visible (or shown) while muted
+    pub fn unmute(&self) -> Result<(), OverlayError> {
+        let manager = self.clone();
+        invoke_on_ui_thread(move || {
+            let visible = match manager.muted.lock() {
+                Ok(mut muted) => muted.take(),
+                Err(_) => return,
+            };
+            for overlay_id in visible.into_iter().flatten() {
+                if let Err(e) = manager.apply_show(&overlay_id) {
+                    overlay_log!(warn, &overlay_id, "Could not show overlay {} while unmuting: {}", overlay_id, e);
+                }
+            }
+        })
+    }
+
+    /// Whether overlays are currently muted (updated once the UI thread has processed `mute`/`unmute`)
+    pub fn is_muted(&self) -> bool {
+        self.muted.lock().map(|muted| muted.is_some()).unwrap_or(false)
+    }
+
+    /// Sets the configuration `create_overlay_quick` starts from
+    pub fn set_defaults(&self, defaults: OverlayConfig) -> Result<(), OverlayError> {
+        *self.defaults.lock().map_err(|_| OverlayError::LockError)? = defaults;
+        Ok(())
+    }
+
+    /// The configuration `create_overlay_quick` starts from
+    pub fn defaults(&self) -> Result<OverlayConfig, OverlayError> {
+        self.defaults.lock().map(|defaults| defaults.clone()).map_err(|_| OverlayError::LockError)
+    }
+
+    /// Creates and shows an overlay with `text` at `(x, y)`.
+    ///
+    /// The text and position given here always win; every other setting (size, color, font,
+    /// transparency, topmost, input passthrough, ...) comes from `set_defaults`, or from
+    /// `OverlayConfig::default()` if none were set. The defaults' own text content is ignored.
+    pub fn create_overlay_quick(&self, text: &str, x: i32, y: i32) -> Result<OverlayId, OverlayError> {
+        let overlay_id = self.create_overlay(self.quick_config(text, x, y)?)?;
+        self.show_overlay(&overlay_id)?;
+        Ok(overlay_id)
+    }
+
+    fn quick_config(&self, text: &str, x: i32, y: i32) -> Result<OverlayConfig, OverlayError> {
+        let mut config = self.defaults()?;
+        config.text.content = text.to_string();
+        config.text.position = (x, y);
+        Ok(config)
+    }
+
+    /// Creates an overlay, giving it a distinct color from a rotating palette when
+    /// `config.text.color` is left empty. Colors follow creation order; the chosen
+    /// one is stored in the overlay's config.
+    pub fn create_overlay_auto_color(&self, mut config: OverlayConfig) -> Result<OverlayId, OverlayError> {
+        if config.text.color.is_empty() {
+            let index = self.identity_colors.fetch_add(1, Ordering::Relaxed);
+            config.text.color = color_utils::identity_color(index).to_string();
+        }
+        self.create_overlay(config)
+    }
+
+    /// Creates and shows an overlay displaying the image at `path` (PNG with alpha supported)
+    pub fn create_image_overlay(&self, path: &str, x: i32, y: i32, width: i32, height: i32) -> Result<OverlayId, OverlayError> {
+        let config = OverlayConfig {
+            text: TextConfig {
+                position: (x, y),
+                ..Default::default()
+            },
+            width,
+            height,
+            image: Some(path.to_string()),
+            ..Default::default()
+        };
+
+        let overlay_id = self.create_overlay(config)?;
+        self.show_overlay(&overlay_id)?;
+        Ok(overlay_id)
+    }
+
+    /// Creates and shows an overlay on the event loop without blocking the caller.
+    ///
+    /// The returned future resolves once the window exists, so it can be awaited from async
+    /// tasks (e.g. a tokio handler) running off the UI thread.
+    pub fn create_overlay_async(&self, config: OverlayConfig) -> impl Future<Output = Result<OverlayId, OverlayError>> + Send {
+        let (sender, receiver) = oneshot::channel();
+        let manager = self.clone();
+        let scheduled = invoke_on_ui_thread(move || {
+            let result = manager.create_overlay(config).and_then(|overlay_id| {
+                manager.show_overlay(&overlay_id)?;
+                Ok(overlay_id)
+            });
+            sender.send(result);
+        });
+
+        async move {
+            scheduled?;
+            receiver.await.unwrap_or(Err(OverlayError::EventLoopNotRunning))
+        }
+    }
+
+    pub fn show_overlay(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        self.ensure_exists(overlay_id)?;
+        if self.debounce_visibility(overlay_id, true)? {
+            return Ok(());
+        }
+        self.apply_show(overlay_id)
+    }
+
+    pub fn hide_overlay(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        self.ensure_exists(overlay_id)?;
+        if self.debounce_visibility(overlay_id, false)? {
+            return Ok(());
+        }
+        self.apply_hide(overlay_id)
+    }
+
+    /// Delays show/hide calls by `window`; calls arriving within it collapse to the
+    /// last requested state. `Duration::ZERO` (the default) applies them immediately.
+    pub fn set_visibility_debounce(&self, window: Duration) -> Result<(), OverlayError> {
+        let mut debounce = self.visibility.lock().map_err(|_| OverlayError::LockError)?;
+        debounce.window = window;
+        Ok(())
+    }
+
+    /// Fails with `OverlayNotFound` unless `overlay_id` names a live overlay, so stale ids are caught
+    pub(crate) fn ensure_exists(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        if self.overlays.lock().map_err(|_| OverlayError::LockError)?.contains_key(overlay_id) {
+            Ok(())
+        } else {
+            Err(OverlayError::OverlayNotFound(overlay_id.clone()))
+        }
+    }
+
+    /// Queues a visibility change when debouncing is enabled; returns whether it was deferred
+    fn debounce_visibility(&self, overlay_id: &OverlayId, visible: bool) -> Result<bool, OverlayError> {
+        let (delay, generation) = {
+            let mut debounce = self.visibility.lock().map_err(|_| OverlayError::LockError)?;
+            if debounce.window.is_zero() {
+                return Ok(false);
+            }
+            (debounce.window, debounce.request(overlay_id, visible))
+        };
+
+        let manager = self.clone();
+        let overlay_id = overlay_id.clone();
+        invoke_on_ui_thread(move || {
+            slint::Timer::single_shot(delay, move || manager.settle_visibility(&overlay_id, generation));
+        })?;
+        Ok(true)
+    }
+
+    fn settle_visibility(&self, overlay_id: &OverlayId, generation: u64) {
+        let visible = match self.visibility.lock() {
+            Ok(mut debounce) => debounce.settle(overlay_id, generation),
+            Err(_) => return,
+        };
+        let result = match visible {
+            Some(true) => self.apply_show(overlay_id),
+            Some(false) => self.apply_hide(overlay_id),
+            None => return,
+        };
+        if let Err(e) = result {
+            overlay_log!(warn, overlay_id, "Failed to apply debounced visibility of {}: {}", overlay_id, e);
+        }
+    }
+
+    fn apply_show(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        // Windows can only be reached from the UI thread
+        if !UI_THREAD.is_current() {
+            let manager = self.clone();
+            let id = overlay_id.clone();
+            return invoke_on_ui_thread(move || {
+                if let Err(e) = manager.apply_show(&id) {
+                    overlay_log!(warn, &id, "Failed to show overlay: {}", e);
+                }
+            });
+        }
+
+        // While muted, remember the request and show it on unmute instead
+        let deferred = match self.muted.lock().map_err(|_| OverlayError::LockError)?.as_mut() {
+            Some(visible) => {
+                visible.insert(overlay_id.clone());
+                true
+            }
+            None => false,
+        };
+        if !deferred {
+            let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+
+            if let Some(overlay) = overlays.get(overlay_id) {
+                if let Some(window) = overlay.window_weak.upgrade() {
+                    if overlay.config.hide_when_empty && overlay.config.text.content.is_empty() {
+                        return Ok(());
+                    }
+                    show_window(&window, &overlay.config, &overlay.applied)?;
+                    overlay_log!(debug, overlay_id, "Shown");
+                }
+            }
+            drop(overlays);
+            if let Err(e) = self.restack_topmost() {
+                log::warn!("Could not restack topmost overlays: {}", e);
+            }
+        }
+
+        self.apply_visibility_to_children(overlay_id, true)
+    }
+
+    /// Re-applies the topmost stacking of the visible overlays by `priority`, since showing a
+    /// window puts it above every other topmost one. Only windows owned by this thread are reachable.
+    fn restack_topmost(&self) -> Result<(), OverlayError> {
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let windows: Vec<_> = overlays
+            .values()
+            .filter(|overlay| overlay.config.always_on_top)
+            .filter_map(|overlay| {
+                let window = overlay.window_weak.upgrade()?;
+                Some((overlay.config.priority, overlay.sequence, window))
+            })
+            .collect();
+        drop(overlays);
+
+        let hwnds: Vec<_> = stacking_order(windows)
+            .into_iter()
+            .filter(|window| window.window().is_visible())
+            .filter_map(|window| window_manager::get_native_handle(window.window()).ok())
+            .collect();
+        window_manager::stack_topmost(&hwnds).map_err(|e| OverlayError::WindowManagerError(e.to_string()))
+    }
+
+    fn apply_hide(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        if !UI_THREAD.is_current() {
+            let manager = self.clone();
+            let id = overlay_id.clone();
+            return invoke_on_ui_thread(move || {
+                if let Err(e) = manager.apply_hide(&id) {
+                    overlay_log!(warn, &id, "Failed to hide overlay: {}", e);
+                }
+            });
+        }
+
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+
+        if let Some(overlay) = overlays.get(overlay_id) {
+            if let Some(window) = overlay.window_weak.upgrade() {
+                window.hide()?;
+                overlay_log!(debug, overlay_id, "Hidden");
+            }
+        }
+        drop(overlays);
+
+        self.apply_visibility_to_children(overlay_id, false)
+    }
+
+    pub fn update_text(&self, overlay_id: &OverlayId, text: &str) -> Result<(), OverlayError> {
+        self.set_text(overlay_id, text, true)
+    }
+
+    /// `update_text`, except that a `manual` update also stops a running playlist
+    pub(crate) fn set_text(&self, overlay_id: &OverlayId, text: &str, manual: bool) -> Result<(), OverlayError> {
+        self.replace_text(overlay_id, manual, |config| match config.max_chars {
+            Some(max_chars) => truncate_text(text, max_chars).into_owned(),
+            None => text.to_string(),
+        })
+    }
+
+    /// Appends `fragment` to the current text, e.g. word by word from a speech-to-text
+    /// engine. Past `max_chars` the oldest text is dropped, so the latest words stay visible.
+    pub fn append_text(&self, overlay_id: &OverlayId, fragment: &str) -> Result<(), OverlayError> {
+        self.replace_text(overlay_id, true, |config| {
+            let text = format!("{}{}", config.text.content, fragment);
+            match config.max_chars {
+                Some(max_chars) => truncate_text_front(&text, max_chars).into_owned(),
+                None => text,
+            }
+        })
+    }
+
+    /// Empties the text, hiding the overlay if `hide_when_empty` is set
+    pub fn clear_text(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        self.update_text(overlay_id, "")
+    }
+
+    /// Computes the new text from the current config and applies it; the read and the
+    /// update happen under one lock, so concurrent appends don't lose fragments. Styled lines
+    /// give way to the plain text, and `manual` updates (anything but the playlist itself)
+    /// stop a running playlist.
+    fn replace_text(
+        &self,
+        overlay_id: &OverlayId,
+        manual: bool,
+        compose: impl FnOnce(&OverlayConfig) -> String,
+    ) -> Result<(), OverlayError> {
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+
+        let Some(overlay) = overlays.get_mut(overlay_id) else {
+            return Err(OverlayError::OverlayNotFound(overlay_id.clone()));
+        };
+        let text = compose(&overlay.config);
+        overlay.config.text.content = text.clone();
+        overlay.config.lines.clear();
+        let text_content = text.clone();
+        let config = overlay.config.clone();
+        let applied = Arc::clone(&overlay.applied);
+        let task_id = overlay_id.clone();
+
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            karaoke::cancel_karaoke(&task_id);
+            if manual {
+                tasks::cancel(&task_id, TaskKind::Playlist);
+            }
+            window.set_karaoke_active(false);
+            window.set_lines_active(false);
+            lock_applied(&applied).set_text(&window, &text_content);
+
+            if config.hide_when_empty {
+                let result = if config.text.content.is_empty() {
+                    window.hide().map_err(OverlayError::from)
+                } else {
+                    show_window(&window, &config, &applied)
+                };
+                if let Err(e) = result {
+                    overlay_log!(warn, &task_id, "Could not toggle visibility of empty overlay: {}", e);
+                }
+            }
+        })?;
+        drop(overlays);
+        MetricsCounters::incr(&self.metrics.text_updates);
+        overlay_log!(debug, overlay_id, "Text set to {:?}", text);
+
+        self.notify(OverlayEvent::TextUpdated {
+            id: overlay_id.clone(),
+            text,
+        });
+
+        Ok(())
+    }
+
+    /// Moves the overlay to `(x, y)`, snapped to the grid set with `set_grid`
+    pub fn update_position(&self, overlay_id: &OverlayId, x: i32, y: i32) -> Result<(), OverlayError> {
+        let target = self.grid_position((x, y));
+        self.move_overlay(overlay_id, |_| target)?
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+        Ok(())
+    }
+
+    /// Makes `update_position` and drags (through `snap_drag_position`) land on multiples of
+    /// `size` pixels, to line overlays up cleanly. `0` (the default) turns the grid off.
+    pub fn set_grid(&self, size: u32) {
+        self.grid.store(size, Ordering::Relaxed);
+    }
+
+    fn grid_position(&self, (x, y): (i32, i32)) -> (i32, i32) {
+        let grid = self.grid.load(Ordering::Relaxed);
+        (window_manager::snap_to_grid(x, grid), window_manager::snap_to_grid(y, grid))
+    }
+
+    /// Moves the overlay by a relative offset, rounded to whole pixels, and returns the new
+    /// position. The read and the update happen under one lock, so concurrent nudges add up.
+    pub fn nudge_position(&self, overlay_id: &OverlayId, dx: f64, dy: f64) -> Result<(i32, i32), OverlayError> {
+        let (dx, dy) = (window_manager::px_round(dx), window_manager::px_round(dy));
+        self.move_overlay(overlay_id, |(x, y)| (x.saturating_add(dx), y.saturating_add(dy)))?
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))
+    }
+
+    /// Computes the new position from the current one and applies it; `None` if the overlay doesn't exist
+    fn move_overlay(
+        &self,
+        overlay_id: &OverlayId,
+        target: impl FnOnce((i32, i32)) -> (i32, i32),
+    ) -> Result<Option<(i32, i32)>, OverlayError> {
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+
+        let Some(overlay) = overlays.get_mut(overlay_id) else {
+            return Ok(None);
+        };
+        if overlay.config.locked {
+            return Err(OverlayError::Locked(overlay_id.clone()));
+        }
+        let (x, y) = target(overlay.config.text.position);
+        overlay.config.text.position = (x, y);
+        let remembered = overlay
+            .config
+            .position_key
+            .clone()
+            .map(|key| (key, position_memory::stored_rect(&overlay.config)));
+
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                let _ = window_manager::set_window_position(hwnd, x, y);
+            }
+        })?;
+        drop(overlays);
+        overlay_log!(debug, overlay_id, "Moved to ({}, {})", x, y);
+
+        if let Some((key, rect)) = remembered {
+            self.queue_position(&key, rect)?;
+        }
+
+        self.notify(OverlayEvent::Moved {
+            id: overlay_id.clone(),
+            x,
+            y,
+        });
+        self.move_linked_children(overlay_id, (x, y))?;
+        Ok(Some((x, y)))
+    }
+
+    /// Keeps overlays within an inset of every monitor, as a percentage of its size on each
+    /// side (e.g. `5.0` for TV overscan). Applies globally to screen clamping and edge
+    /// snapping; `0.0` (the default) uses the full work area.
+    pub fn set_safe_area(&self, inset_percent: f32) {
+        let inset = if inset_percent.is_nan() { 0.0 } else { inset_percent.clamp(0.0, 50.0) };
+        SAFE_AREA_INSET.store(inset.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Sets how close, in pixels, a dragged overlay must be to a monitor edge or another
+    /// overlay before it snaps to it. `0` disables snapping.
+    pub fn set_snap_threshold(&self, px: u32) {
+        self.snap_threshold.store(px, Ordering::Relaxed);
+    }
+
+    /// Adjusts a drag position so the overlay snaps to nearby monitor edges and other
+    /// overlays, then to the grid if one is set (the grid wins). Meant to be called from a
+    /// drag handler before `update_position`.
+    pub fn snap_drag_position(&self, overlay_id: &OverlayId, x: i32, y: i32) -> Result<(i32, i32), OverlayError> {
+        let threshold = self.snap_threshold.load(Ordering::Relaxed);
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+        if threshold == 0 {
+            return Ok(self.grid_position((x, y)));
         }
+
+        let rect_of = |config: &OverlayConfig| window_manager::ScreenRect {
+            x: config.text.position.0,
+            y: config.text.position.1,
+            width: config.width,
+            height: config.height,
+        };
+        let mut targets: Vec<_> = usable_monitors()
+            .into_iter()
+            .map(|monitor| monitor.work_area)
+            .collect();
+        targets.extend(
+            overlays
+                .iter()
+                .filter(|(id, _)| *id != overlay_id)
+                .map(|(_, other)| rect_of(&other.config)),
+        );
+
+        let rect = window_manager::ScreenRect { x, y, ..rect_of(&overlay.config) };
+        let snapped = window_manager::snap_rect(rect, &targets, threshold as i32);
+        Ok(self.grid_position((snapped.x, snapped.y)))
+    }
+
+    pub fn remove_overlay(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+
+        if overlays.get(overlay_id).is_some_and(|overlay| overlay.config.locked) {
+            return Err(OverlayError::Locked(overlay_id.clone()));
+        }
+
+        if let Some(overlay) = overlays.remove(overlay_id) {
+            drop(overlays);
+            self.finish_removal(overlay_id, overlay)?;
+        }
+
+        Ok(())
+    }
+
+    /// Everything that follows taking an overlay out of `overlays`: links, position memory,
+    /// timers and bindings, the window itself and the `Removed` event
+    fn finish_removal(&self, overlay_id: &OverlayId, overlay: OverlayWindow) -> Result<(), OverlayError> {
+        MetricsCounters::incr(&self.metrics.overlays_removed);
+        overlay_log!(debug, overlay_id, "Removed overlay {}", overlay_id);
+        self.links.lock().map_err(|_| OverlayError::LockError)?.forget(overlay_id);
+
+        if let Some(key) = &overlay.config.position_key {
+            // Prefer where the window actually ended up, when it can be read from this thread
+            let mut rect = position_memory::stored_rect(&overlay.config);
+            if let Some(native) = overlay
+                .window_weak
+                .upgrade()
+                .and_then(|window| window_manager::get_native_handle(window.window()).ok())
+                .and_then(|hwnd| window_manager::get_window_rect(hwnd).ok())
+            {
+                rect.x = native.x;
+                rect.y = native.y;
+            }
+            self.remember_position(key, rect)?;
+        }
+
+        // Windows all live on the UI thread; if that's us, release it now
+        let released = if UI_THREAD.is_current() {
+            release_overlay_resources(overlay_id);
+            Ok(())
+        } else {
+            let id_clone = overlay_id.clone();
+            invoke_on_ui_thread(move || release_overlay_resources(&id_clone))
+        };
+
+        self.notify(OverlayEvent::Removed { id: overlay_id.clone() });
+        released
+    }
+
+    /// Removes every overlay that isn't locked, along with its timers and bindings, and
+    /// returns how many were removed
+    pub fn remove_all(&self) -> Result<usize, OverlayError> {
+        let unlocked: Vec<OverlayId> = self
+            .overlays
+            .lock()
+            .map_err(|_| OverlayError::LockError)?
+            .iter()
+            .filter(|(_, overlay)| !overlay.config.locked)
+            .map(|(overlay_id, _)| overlay_id.clone())
+            .collect();
+        for overlay_id in &unlocked {
+            self.remove_overlay(overlay_id)?;
+        }
+        Ok(unlocked.len())
+    }
+
+    /// Tears everything down: removes every overlay, locked ones included, and stops all
+    /// background work (per-overlay timers and bindings, the watchdog and topmost
+    /// enforcement) so no callback fires afterwards. The event loop itself keeps running.
+    pub fn shutdown(&self) -> Result<(), OverlayError> {
+        for overlay in self.overlays.lock().map_err(|_| OverlayError::LockError)?.values_mut() {
+            overlay.config.locked = false;
+        }
+        self.remove_all()?;
+
+        let stop = || {
+            WATCHDOG_TIMER.with(|holder| holder.borrow_mut().take());
+            TOPMOST_TIMER.with(|holder| holder.borrow_mut().take());
+            tasks::cancel_all();
+        };
+        if UI_THREAD.is_current() {
+            stop();
+            Ok(())
+        } else {
+            invoke_on_ui_thread(stop)
+        }
+    }
+
+    /// Locks or unlocks an overlay against moves, config changes and removal.
+    ///
+    /// This is the only call that bypasses the lock, so it should only be reachable by trusted callers.
+    pub fn set_locked(&self, overlay_id: &OverlayId, locked: bool) -> Result<(), OverlayError> {
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+
+        match overlays.get_mut(overlay_id) {
+            Some(overlay) => {
+                overlay.config.locked = locked;
+                Ok(())
+            }
+            None => Err(OverlayError::OverlayNotFound(overlay_id.clone())),
+        }
+    }
+
+    /// Puts every current and future overlay on top, click-through and locked (no moves,
+    /// config changes or removal) for locked-down displays. Calling it again is a no-op.
+    pub fn enter_presentation_mode(&self) -> Result<(), OverlayError> {
+        let mut presentation = self.presentation.lock().map_err(|_| OverlayError::LockError)?;
+        if presentation.is_some() {
+            return Ok(());
+        }
+
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let mut backups = HashMap::new();
+        for (overlay_id, overlay) in overlays.iter_mut() {
+            backups.insert(overlay_id.clone(), PresentationBackup::force(&mut overlay.config));
+            self.apply_input_and_topmost(overlay)?;
+        }
+        *presentation = Some(backups);
+        Ok(())
+    }
+
+    /// Leaves presentation mode, restoring each overlay's own input, topmost and lock settings
+    pub fn exit_presentation_mode(&self) -> Result<(), OverlayError> {
+        let mut presentation = self.presentation.lock().map_err(|_| OverlayError::LockError)?;
+        let Some(backups) = presentation.take() else {
+            return Ok(());
+        };
+
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        for (overlay_id, backup) in backups {
+            if let Some(overlay) = overlays.get_mut(&overlay_id) {
+                backup.restore(&mut overlay.config);
+                self.apply_input_and_topmost(overlay)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether transparent overlays will actually be see-through here. When this is `false`,
+    /// transparent overlays are drawn on their `opaque_background` instead.
+    pub fn transparency_available(&self) -> bool {
+        window_manager::composition_enabled()
+    }
+
+    /// Whether presentation mode is active
+    pub fn is_presentation_mode(&self) -> bool {
+        self.presentation.lock().map(|presentation| presentation.is_some()).unwrap_or(false)
+    }
+
+    /// Pushes the stored `ignore_input` and `always_on_top` settings to the native window
+    fn apply_input_and_topmost(&self, overlay: &OverlayWindow) -> Result<(), OverlayError> {
+        let (ignore_input, always_on_top) = (overlay.config.ignore_input, overlay.config.always_on_top);
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                let _ = window_manager::set_input_passthrough(hwnd, ignore_input);
+                let _ = window_manager::set_always_on_top(hwnd, always_on_top);
+            }
+        })
+    }
+
+    /// Sets the window title, which screen capture tools use to list windows
+    pub fn set_title(&self, overlay_id: &OverlayId, title: &str) -> Result<(), OverlayError> {
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get_mut(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+
+        overlay.config.title = Some(title.to_string());
+        let title = title.to_string();
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            window.set_window_title(title.into());
+        })
+    }
+
+    /// Draws a 1px border and the overlay id on every overlay, current and future, so
+    /// transparent or empty overlays can be located while laying out a scene. Off by default.
+    pub fn set_debug_borders(&self, enabled: bool) -> Result<(), OverlayError> {
+        self.debug_borders.store(enabled, Ordering::Relaxed);
+        let windows: Vec<Weak<OverlayUI>> = self
+            .overlays
+            .lock()
+            .map_err(|_| OverlayError::LockError)?
+            .values()
+            .map(|overlay| overlay.window_weak.clone())
+            .collect();
+        invoke_on_ui_thread(move || {
+            for window in windows.iter().filter_map(|window| window.upgrade()) {
+                window.set_debug_borders(enabled);
+            }
+        })
+    }
+
+    /// Rotates the text clockwise by `degrees` around the box center, growing the window to
+    /// the rotated bounding box so nothing is clipped
+    pub fn set_rotation(&self, overlay_id: &OverlayId, degrees: f32) -> Result<(), OverlayError> {
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get_mut(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+        if overlay.config.locked {
+            return Err(OverlayError::Locked(overlay_id.clone()));
+        }
+
+        overlay.config.rotation_deg = normalize_rotation(degrees);
+        let rotation = overlay.config.rotation_deg;
+        let (width, height) = overlay.config.window_size();
+        let event = OverlayEvent::ConfigUpdated {
+            id: overlay_id.clone(),
+            config: overlay.config.clone(),
+        };
+        let applied = Arc::clone(&overlay.applied);
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            window.set_rotation_deg(rotation);
+            lock_applied(&applied).set_size(&window, width, height);
+        })?;
+        drop(overlays);
+
+        self.notify(event);
+        Ok(())
+    }
+
+    /// Gives the overlay keyboard focus, e.g. for a notification with an input field.
+    /// Click-through overlays (`ignore_input`) are refused with `NotInteractive` so they never
+    /// steal focus. Does nothing on backends without a Win32 window handle.
+    pub fn focus(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+        if overlay.config.ignore_input {
+            return Err(OverlayError::NotInteractive(overlay_id.clone()));
+        }
+
+        let id = overlay_id.clone();
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            let Ok(hwnd) = window_manager::get_native_handle(window.window()) else {
+                return;
+            };
+            match window_manager::focus_window(hwnd) {
+                Ok(previous) => FOCUS_RETURN.with(|focus| {
+                    focus.borrow_mut().insert(id, previous);
+                }),
+                Err(e) => overlay_log!(warn, &id, "Could not focus overlay {}: {}", id, e),
+            }
+        })
+    }
+
+    /// Takes keyboard focus away from the overlay, returning it to the window that had it
+    /// before `focus`. Does nothing if the overlay isn't focused.
+    pub fn blur(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+
+        let id = overlay_id.clone();
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            let previous = FOCUS_RETURN.with(|focus| focus.borrow_mut().remove(&id));
+            if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                if let Err(e) = window_manager::blur_window(hwnd, previous) {
+                    overlay_log!(warn, &id, "Could not blur overlay {}: {}", id, e);
+                }
+            }
+        })
+    }
+
+    /// Flashes the overlay's taskbar button and caption to draw attention to it.
+    ///
+    /// Does nothing on backends without a Win32 window handle.
+    pub fn request_attention(&self, overlay_id: &OverlayId, mode: window_manager::AttentionMode) -> Result<(), OverlayError> {
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+
+        let overlay = overlays
+            .get(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                let _ = window_manager::flash_window(hwnd, mode);
+            }
+        })
+    }
+
+    /// Stacks the overlay directly above or below another window instead of globally
+    /// topmost, e.g. above a game but below system dialogs. If the target window does
+    /// not exist the overlay is left where it is.
+    pub fn set_relative_zorder(
+        &self,
+        overlay_id: &OverlayId,
+        target: window_manager::ZOrderTarget,
+        above: bool,
+    ) -> Result<(), OverlayError> {
+        let target_hwnd = window_manager::find_window(&target)
+            .ok_or_else(|| OverlayError::WindowManagerError(format!("Target window not found: {:?}", target)))?;
+
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                if let Err(e) = window_manager::set_relative_zorder(hwnd, target_hwnd, above) {
+                    overlay_log!(warn, &window.get_debug_label(), "Failed to change z-order: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Hides the overlay from screen recording and streaming software while keeping it
+    /// visible locally. Requires Windows 10 2004+; a no-op elsewhere.
+    pub fn set_capture_excluded(&self, overlay_id: &OverlayId, excluded: bool) -> Result<(), OverlayError> {
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                if let Err(e) = window_manager::set_capture_excluded(hwnd, excluded) {
+                    overlay_log!(warn, &window.get_debug_label(), "Failed to change capture exclusion: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Blurs whatever is behind the overlay (acrylic), tinted with `tint` (e.g. `#80202020`).
+    ///
+    /// Uses the Windows 11 backdrop or the Windows 10 accent blur; where neither is
+    /// available the overlay falls back to a solid background in the tint color.
+    pub fn enable_acrylic(&self, overlay_id: &OverlayId, tint: String) -> Result<(), OverlayError> {
+        let Some(tint_argb) = color_utils::parse_argb(&tint) else {
+            return Err(OverlayError::InvalidColor(tint));
+        };
+        let tint_brush = slint::Brush::from(slint::Color::from_argb_encoded(tint_argb));
+
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            let backdrop = match window_manager::get_native_handle(window.window()) {
+                Ok(hwnd) => window_manager::enable_acrylic(hwnd, tint_argb),
+                Err(_) => window_manager::Backdrop::Unsupported,
+            };
+
+            match backdrop {
+                window_manager::Backdrop::AccentAcrylic => {
+                    window.set_box_background(slint::Brush::from(slint::Color::from_argb_u8(0, 0, 0, 0)));
+                }
+                window_manager::Backdrop::SystemAcrylic => window.set_box_background(tint_brush),
+                window_manager::Backdrop::Unsupported => {
+                    overlay_log!(
+                        warn,
+                        &window.get_debug_label(),
+                        "Acrylic backdrop not supported, using a solid background instead"
+                    );
+                    window.set_box_background(tint_brush);
+                }
+            }
+        })?;
+
+        Ok(())
     }
 
-    pub fn create_overlay(&self, config: OverlayConfig) -> Result<OverlayId, OverlayError> {
-        if !color_utils::is_valid_color(&config.text.color) {
-            return Err(OverlayError::InvalidColor(config.text.color.clone()));
-        }
+    /// Mirrors the system clipboard into the overlay text, polling every `poll`.
+    ///
+    /// Only text content is picked up; anything else on the clipboard is ignored.
+    /// Binding an already bound overlay replaces the previous binding.
+    pub fn bind_clipboard(&self, overlay_id: &OverlayId, poll: Duration) -> Result<(), OverlayError> {
+        self.ensure_exists(overlay_id)?;
+        let manager = self.clone();
+        let id = overlay_id.clone();
 
-        let overlay_id = Uuid::new_v4().to_string();
+        invoke_on_ui_thread(move || {
+            // Removed while this was queued; don't leave a timer polling for a dead overlay
+            if manager.ensure_exists(&id).is_err() {
+                return;
+            }
+            let mut clipboard = match arboard::Clipboard::new() {
+                Ok(clipboard) => clipboard,
+                Err(e) => {
+                    overlay_log!(warn, &id, "Could not open clipboard for overlay {}: {}", id, e);
+                    return;
+                }
+            };
+            let mut last_text = clipboard.get_text().ok();
 
-        let ui = OverlayUI::new()?;
+            let timer = slint::Timer::default();
+            let timer_id = id.clone();
+            timer.start(slint::TimerMode::Repeated, poll, move || {
+                let Ok(text) = clipboard.get_text() else {
+                    return;
+                };
+                if last_text.as_deref() == Some(text.as_str()) {
+                    return;
+                }
+
+                // Same path as update_text, so max_chars, hide_when_empty and playlists apply
+                if let Err(e) = manager.update_text(&timer_id, &text) {
+                    overlay_log!(warn, &timer_id, "Could not show clipboard text: {}", e);
+                }
+                last_text = Some(text);
+            });
 
-        ui.set_text_content(config.text.content.clone().into());
-        ui.set_font_size(config.text.font_size);
+            tasks::register(&id, TaskKind::Clipboard, timer);
+        })?;
 
-        let color_value = color_utils::hex_to_argb_u32(&config.text.color);
+        Ok(())
+    }
 
-        ui.set_text_color(slint::Brush::from(slint::Color::from_argb_encoded(color_value)));
+    /// Stops mirroring the clipboard into the overlay
+    pub fn unbind_clipboard(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        let id = overlay_id.clone();
+        invoke_on_ui_thread(move || tasks::cancel(&id, TaskKind::Clipboard))?;
+        Ok(())
+    }
 
-        WINDOW_HOLDER.with(|holder| {
-            holder.borrow_mut().insert(overlay_id.clone(), ui.clone_strong());
-        });
+    /// Puts the overlay's live configuration on the clipboard as pretty JSON, for sharing
+    /// it or attaching it to a bug report. `create_from_clipboard` turns it back into an overlay.
+    pub fn copy_config_to_clipboard(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        let config = self.get_overlay_config(overlay_id)?;
+        let json = serde_json::to_string_pretty(&config).map_err(|e| OverlayError::Clipboard(e.to_string()))?;
+        arboard::Clipboard::new()
+            .and_then(|mut clipboard| clipboard.set_text(json))
+            .map_err(|e| OverlayError::Clipboard(e.to_string()))
+    }
 
-        let overlay_window = OverlayWindow {
-            window_weak: ui.as_weak(),
-            config: config.clone(),
-        };
+    /// Creates an overlay (hidden until `show_overlay`) from a configuration copied with
+    /// `copy_config_to_clipboard`. Anything else on the clipboard fails with `Clipboard`.
+    pub fn create_from_clipboard(&self) -> Result<OverlayId, OverlayError> {
+        let text = arboard::Clipboard::new()
+            .and_then(|mut clipboard| clipboard.get_text())
+            .map_err(|e| OverlayError::Clipboard(e.to_string()))?;
+        self.create_overlay(config_from_json(&text)?)
+    }
 
-        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
-        overlays.insert(overlay_id.clone(), overlay_window);
+    pub fn list_overlays(&self) -> Vec<OverlayId> {
+        self.overlays.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Lists overlay ids in creation order, oldest first (reverse it for "most recent first")
+    pub fn list_overlays_ordered(&self) -> Result<Vec<OverlayId>, OverlayError> {
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let mut ordered: Vec<_> = overlays.iter().collect();
+        ordered.sort_by_key(|(_, overlay)| overlay.sequence);
+        Ok(ordered.into_iter().map(|(overlay_id, _)| overlay_id.clone()).collect())
+    }
 
+    /// Lists every overlay with a text preview, position, size and visibility, built under a single lock
+    pub fn list_overlays_detailed(&self) -> Result<Vec<OverlaySummary>, OverlayError> {
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
 
-        Ok(overlay_id)
+        Ok(overlays
+            .iter()
+            .map(|(overlay_id, overlay)| OverlaySummary {
+                id: overlay_id.clone(),
+                text: truncate_text(&overlay.config.text.content, OverlaySummary::TEXT_PREVIEW_CHARS).into_owned(),
+                position: overlay.config.text.position,
+                size: (overlay.config.width, overlay.config.height),
+                visible: overlay.window_weak.upgrade().map(|window| window.window().is_visible()),
+            })
+            .collect())
     }
 
-    pub fn show_overlay(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+    pub fn get_overlay_config(&self, overlay_id: &OverlayId) -> Result<OverlayConfig, OverlayError> {
         let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
 
         if let Some(overlay) = overlays.get(overlay_id) {
+            let mut config = overlay.config.clone();
             if let Some(window) = overlay.window_weak.upgrade() {
-                window.set_win_width(overlay.config.width as f32);
-                window.set_win_height(overlay.config.height as f32);
-                window.set_font_size(overlay.config.text.font_size);
-
-                window.show()?;
-
-                // Set window position and apply properties
-                if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
-                    let _ = window_manager::apply_window_properties(
-                        hwnd,
-                        overlay.config.transparent,
-                        overlay.config.always_on_top,
-                        overlay.config.ignore_input,
-                    );
-                    let (x, y) = overlay.config.text.position;
-                    let _ = window_manager::set_window_position(hwnd, x, y);
-                }
+                config.text.content = window.get_text_content().to_string();
             }
+            Ok(config)
+        } else {
+            Err(OverlayError::OverlayNotFound(overlay_id.clone()))
         }
-
-        Ok(())
     }
 
-    pub fn hide_overlay(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+    /// The colors the overlay is drawn with right now, resolved through the same parsing
+    /// and fallback as rendering, so an editor can preview its true appearance. Named,
+    /// functional and percent-alpha colors all come back as plain ARGB.
+    pub fn rendered_colors(&self, overlay_id: &OverlayId) -> Result<RenderedColors, OverlayError> {
         let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
 
-        if let Some(overlay) = overlays.get(overlay_id) {
-            if let Some(window) = overlay.window_weak.upgrade() {
-                window.hide()?;
+        let text = lock_applied(&overlay.applied)
+            .color
+            .unwrap_or_else(|| overlay.config.text_color_argb());
+        let background = overlay
+            .window_weak
+            .upgrade()
+            .map(|window| window.get_box_background().color().as_argb_encoded());
+        Ok(RenderedColors { text, background })
+    }
+
+    /// Removes overlays whose window no longer exists and returns how many were pruned.
+    ///
+    /// Must be called from the UI thread that created the overlays.
+    pub fn prune_dead(&self) -> Result<usize, OverlayError> {
+        let pruned = self.remove_dead()?;
+        if pruned > 0 {
+            log::info!("Pruned {} dead overlay(s)", pruned);
+        }
+        Ok(pruned)
+    }
+
+    /// Removes the dead overlays the way `remove_overlay` does, so their links, tasks and
+    /// remembered positions are cleaned up too
+    fn remove_dead(&self) -> Result<usize, OverlayError> {
+        let dead = take_dead_overlays(&self.overlays)?;
+        let pruned = dead.len();
+        for (overlay_id, overlay) in dead {
+            if let Err(e) = self.finish_removal(&overlay_id, overlay) {
+                overlay_log!(warn, &overlay_id, "Could not clean up pruned overlay {}: {}", overlay_id, e);
             }
         }
+        Ok(pruned)
+    }
 
+    /// Starts a timer on the event loop that periodically calls `prune_dead`.
+    ///
+    /// Calling it again replaces the previous watchdog.
+    pub fn start_watchdog(&self, interval: Duration) -> Result<(), OverlayError> {
+        let manager = self.clone();
+        invoke_on_ui_thread(move || {
+            let timer = slint::Timer::default();
+            timer.start(slint::TimerMode::Repeated, interval, move || {
+                match manager.remove_dead() {
+                    Ok(0) => {}
+                    Ok(pruned) => log::info!("Watchdog pruned {} dead overlay(s)", pruned),
+                    Err(e) => log::warn!("Watchdog could not prune overlays: {}", e),
+                }
+            });
+            WATCHDOG_TIMER.with(|holder| *holder.borrow_mut() = Some(timer));
+        })?;
         Ok(())
     }
 
-    pub fn update_text(&self, overlay_id: &OverlayId, text: &str) -> Result<(), OverlayError> {
-        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
-
-        if let Some(overlay) = overlays.get_mut(overlay_id) {
-            overlay.config.text.content = text.to_string();
-            let text_content = text.to_string();
+    /// Stops the watchdog started by `start_watchdog`, if any.
+    pub fn stop_watchdog(&self) -> Result<(), OverlayError> {
+        invoke_on_ui_thread(|| {
+            WATCHDOG_TIMER.with(|holder| holder.borrow_mut().take());
+        })?;
+        Ok(())
+    }
 
-            self.execute_ui_action(&overlay.window_weak, move |window| {
-                window.set_text_content(text_content.into());
-            })?;
+    /// Re-applies always-on-top to every visible always-on-top overlay every `interval`, in
+    /// `priority` order, for fullscreen games and apps that push themselves above topmost
+    /// windows. Off by default; calling it again changes the interval and `Duration::ZERO`
+    /// turns it off.
+    pub fn enable_topmost_enforcement(&self, interval: Duration) -> Result<(), OverlayError> {
+        if interval.is_zero() {
+            return self.disable_topmost_enforcement();
         }
+        let manager = self.clone();
+        invoke_on_ui_thread(move || {
+            let timer = slint::Timer::default();
+            timer.start(slint::TimerMode::Repeated, interval, move || {
+                if let Err(e) = manager.restack_topmost() {
+                    log::warn!("Could not re-apply topmost to overlays: {}", e);
+                }
+            });
+            TOPMOST_TIMER.with(|holder| *holder.borrow_mut() = Some(timer));
+        })
+    }
 
-        Ok(())
+    /// Stops the timer started by `enable_topmost_enforcement`, if any
+    pub fn disable_topmost_enforcement(&self) -> Result<(), OverlayError> {
+        invoke_on_ui_thread(|| {
+            TOPMOST_TIMER.with(|holder| holder.borrow_mut().take());
+        })
     }
 
-    pub fn update_position(&self, overlay_id: &OverlayId, x: i32, y: i32) -> Result<(), OverlayError> {
-        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+    /// Returns a snapshot of the activity counters
+    pub fn metrics(&self) -> Metrics {
+        self.metrics.snapshot()
+    }
 
-        if let Some(overlay) = overlays.get_mut(overlay_id) {
-            overlay.config.text.position = (x, y);
-        }
+    /// Renders the activity counters in Prometheus text format, suitable for a `/metrics` endpoint
+    pub fn metrics_prometheus(&self) -> Result<String, OverlayError> {
+        let active = self.overlays.lock().map_err(|_| OverlayError::LockError)?.len();
+        Ok(self.metrics.snapshot().to_prometheus(active))
+    }
 
-        Ok(())
+    /// Records a request handled by an embedding API server
+    pub fn record_api_request(&self) {
+        MetricsCounters::incr(&self.metrics.api_requests);
     }
 
-    pub fn remove_overlay(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
-        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+    /// Records a call handled by an embedding MCP server
+    pub fn record_mcp_call(&self) {
+        MetricsCounters::incr(&self.metrics.mcp_calls);
+    }
 
-        if overlays.remove(overlay_id).is_some() {
-            let id_clone = overlay_id.clone();
-            let _ = slint::invoke_from_event_loop(move || {
-                WINDOW_HOLDER.with(|holder| {
-                    holder.borrow_mut().remove(&id_clone);
-                });
-            });
+    /// Replaces an overlay's configuration and applies it to the live window
+    pub fn update_config(&self, overlay_id: &OverlayId, config: OverlayConfig) -> Result<(), OverlayError> {
+        if config.fallback_color.is_none() && color_utils::parse_argb(&config.text.color).is_none() {
+            return Err(OverlayError::InvalidColor(config.text.color.clone()));
+        }
+        let mut config = config;
+        config.validate();
+
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get_mut(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+        if overlay.config.locked {
+            return Err(OverlayError::Locked(overlay_id.clone()));
         }
+        let image_changed = overlay.config.image != config.image;
+        let alpha_changed = overlay.config.window_alpha != config.window_alpha;
+        if let (true, Some(path)) = (image_changed, &config.image) {
+            // Checked here so a bad path fails like it does in `create_overlay`; the image itself
+            // can't leave this thread, so the UI thread loads it again
+            slint::Image::load_from_path(std::path::Path::new(path)).map_err(|_| OverlayError::ImageLoad(path.clone()))?;
+        }
+        let quality_changed = overlay.config.high_quality_text != config.high_quality_text;
+        let background_changed = (&overlay.config.background_image, overlay.config.background_fit)
+            != (&config.background_image, config.background_fit);
+        overlay.config = config.clone();
+        let event = OverlayEvent::ConfigUpdated {
+            id: overlay_id.clone(),
+            config: config.clone(),
+        };
+
+        let applied = Arc::clone(&overlay.applied);
+        let animation_id = overlay_id.clone();
+
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            animation::cancel_color_animation(&animation_id);
+            {
+                let mut applied = lock_applied(&applied);
+                applied.set_text(&window, &config.text.content);
+                applied.set_font_size(&window, config.text.font_size);
+                applied.set_color(&window, config.text_color_argb());
+                let (width, height) = config.window_size();
+                applied.set_size(&window, width, height);
+            }
+            window.set_pixel_snap(config.pixel_snap);
+            window.set_rotation_deg(config.rotation_deg);
+            window.set_box_padding(config.padding);
+            window.set_wrap_text(config.wrap_text);
+            lines::apply_lines(&window, &config);
+            set_drop_shadow(&window, &config.text);
+            if quality_changed {
+                set_render_quality(&window, config.high_quality_text);
+            }
+            if background_changed {
+                set_background_image(&window, &config);
+            }
+            if image_changed {
+                let image = config
+                    .image
+                    .as_deref()
+                    .and_then(|path| slint::Image::load_from_path(std::path::Path::new(path)).ok());
+                match image {
+                    Some(image) => {
+                        window.set_image_source(image);
+                        window.set_show_image(true);
+                        window.set_box_background(slint::Brush::from(slint::Color::from_argb_u8(0, 0, 0, 0)));
+                    }
+                    None => window.set_show_image(false),
+                }
+            }
+            if let Some(title) = &config.title {
+                window.set_window_title(title.as_str().into());
+            }
+
+            if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                if config.transparent {
+                    let _ = window_manager::create_transparent_click_through_window(hwnd);
+                }
+                // Also when going back to `PER_PIXEL_ALPHA`, which would otherwise leave it dimmed
+                if config.transparent || alpha_changed {
+                    let _ = window_manager::set_window_transparency(hwnd, config.window_alpha);
+                }
+                if config.always_on_top {
+                    let _ = window_manager::set_always_on_top(hwnd, true);
+                }
+            }
+        })?;
+        drop(overlays);
+        overlay_log!(debug, overlay_id, "Config updated");
+
+        self.notify(event);
 
         Ok(())
     }
 
-    pub fn list_overlays(&self) -> Vec<OverlayId> {
-        self.overlays.lock().unwrap().keys().cloned().collect()
+    /// Whether the overlay's current text fits inside its box, padding included, so a UI can
+    /// warn about clipping before showing it. With `wrap_text` the text is measured wrapped to
+    /// the box width; otherwise lines only break at `\n`, so a long line that doesn't fit is
+    /// reported. Returns `None` if the text can't be measured, e.g. when called off the UI thread.
+    pub fn text_fits(&self, overlay_id: &OverlayId) -> Option<bool> {
+        let overlays = self.overlays.lock().ok()?;
+        let overlay = overlays.get(overlay_id)?;
+        let window = overlay.window_weak.upgrade()?;
+        let text = (window.get_text_extent_width(), window.get_text_extent_height());
+        let config = &overlay.config;
+        Some(text_fits_box(text, (config.width as f32, config.height as f32), config.padding))
     }
 
-    pub fn get_overlay_config(&self, overlay_id: &OverlayId) -> Result<OverlayConfig, OverlayError> {
-        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+    /// Reads the overlay's actual on-screen rectangle as `(x, y, width, height)`.
+    ///
+    /// This reflects DPI scaling and clamping, so it may differ from the stored config.
+    /// Returns `None` if the window isn't available, e.g. when called off the UI thread.
+    pub fn get_native_rect(&self, overlay_id: &OverlayId) -> Option<(i32, i32, i32, i32)> {
+        let overlays = self.overlays.lock().ok()?;
+        let window = overlays.get(overlay_id)?.window_weak.upgrade()?;
+        let hwnd = window_manager::get_native_handle(window.window()).ok()?;
+        let rect = window_manager::get_window_rect(hwnd).ok()?;
+        Some((rect.x, rect.y, rect.width, rect.height))
+    }
 
-        if let Some(overlay) = overlays.get(overlay_id) {
-            let mut config = overlay.config.clone();
-            if let Some(window) = overlay.window_weak.upgrade() {
-                config.text.content = window.get_text_content().to_string();
+    /// Collects the overlay's text, color, font size, native rect, visibility and topmost
+    /// state in one snapshot. Returns `None` if the overlay doesn't exist.
+    pub fn debug_dump(&self, overlay_id: &OverlayId) -> Option<OverlayDebugInfo> {
+        let overlays = self.overlays.lock().ok()?;
+        let overlay = overlays.get(overlay_id)?;
+        let config = &overlay.config;
+        let mut info = OverlayDebugInfo {
+            id: overlay_id.clone(),
+            text: config.text.content.clone(),
+            color_argb: config.text_color_argb(),
+            font_size: config.text.font_size,
+            native_rect: None,
+            visible: None,
+            topmost: config.always_on_top,
+        };
+
+        if let Some(window) = overlay.window_weak.upgrade() {
+            info.text = window.get_text_content().to_string();
+            info.visible = Some(window.window().is_visible());
+            if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+                info.native_rect = window_manager::get_window_rect(hwnd).ok();
+                info.topmost = window_manager::is_topmost(hwnd);
             }
-            Ok(config)
-        } else {
-            Err(OverlayError::OverlayNotFound(overlay_id.clone()))
         }
+        Some(info)
+    }
+
+    /// Overrides the Slint scale factor of the overlay, e.g. `1.0` for pixel-exact HUDs or
+    /// `2.0` for supersampled text. This replaces the DPI-derived factor until Windows reports
+    /// a new one, for instance when the overlay moves to a monitor with different scaling.
+    pub fn set_scale_factor(&self, overlay_id: &OverlayId, factor: f32) -> Result<(), OverlayError> {
+        if !(factor > 0.0 && factor.is_finite()) {
+            return Err(OverlayError::InvalidScaleFactor(factor));
+        }
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            window
+                .window()
+                .dispatch_event(slint::platform::WindowEvent::ScaleFactorChanged { scale_factor: factor });
+        })
     }
 
-    fn apply_window_properties(&self, overlay_id: &OverlayId, config: &OverlayConfig) -> Result<(), OverlayError> {
+    /// Switches `high_quality_text` on every overlay at once, e.g. to lighten a scene with
+    /// many overlays on a low-powered machine, and returns how many were switched. Locked
+    /// overlays are skipped.
+    pub fn set_render_quality_all(&self, high_quality: bool) -> Result<usize, OverlayError> {
         let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
-        if let Some(overlay) = overlays.get_mut(overlay_id) {
-            overlay.config = config.clone();
+        let mut windows = Vec::new();
+        let mut events = Vec::new();
+        for (overlay_id, overlay) in overlays.iter_mut() {
+            if overlay.config.locked || overlay.config.high_quality_text == high_quality {
+                continue;
+            }
+            overlay.config.high_quality_text = high_quality;
+            windows.push(overlay.window_weak.clone());
+            events.push(OverlayEvent::ConfigUpdated {
+                id: overlay_id.clone(),
+                config: overlay.config.clone(),
+            });
+        }
 
-            let transparent = config.transparent;
-            let always_on_top = config.always_on_top;
+        invoke_on_ui_thread(move || {
+            for window in windows.iter().filter_map(Weak::upgrade) {
+                set_render_quality(&window, high_quality);
+            }
+        })?;
+        drop(overlays);
 
-            self.execute_ui_action(&overlay.window_weak, move |window| {
-                if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
-                    if transparent {
-                        let _ = window_manager::create_transparent_click_through_window(hwnd);
-                    }
-                    if always_on_top {
-                        let _ = window_manager::set_always_on_top(hwnd, true);
-                    }
-                }
-            })?;
+        let switched = events.len();
+        for event in events {
+            self.notify(event);
         }
+        Ok(switched)
+    }
+
+    /// Reads the overlay's current Slint scale factor.
+    /// Returns `None` if the window isn't available, e.g. when called off the UI thread.
+    pub fn get_scale_factor(&self, overlay_id: &OverlayId) -> Option<f32> {
+        let overlays = self.overlays.lock().ok()?;
+        let window = overlays.get(overlay_id)?.window_weak.upgrade()?;
+        Some(window.window().scale_factor())
+    }
 
+    /// Registers a callback invoked after every create, text update, move, config update and removal.
+    ///
+    /// Only one observer is kept; setting a new one replaces the previous. The callback runs on
+    /// the thread that made the change, after the change has been applied, so it may call back
+    /// into the manager.
+    pub fn set_observer(&self, observer: Box<dyn Fn(&OverlayEvent) + Send + Sync>) -> Result<(), OverlayError> {
+        *self.observer.lock().map_err(|_| OverlayError::LockError)? = Some(Arc::from(observer));
         Ok(())
     }
 
+    /// Removes the observer set with `set_observer`
+    pub fn clear_observer(&self) -> Result<(), OverlayError> {
+        *self.observer.lock().map_err(|_| OverlayError::LockError)? = None;
+        Ok(())
+    }
+
+    fn notify(&self, event: OverlayEvent) {
+        notify_observer(&self.observer, event);
+    }
+
     fn execute_ui_action<F>(&self, window_weak: &Weak<OverlayUI>, action: F) -> Result<(), OverlayError>
     where
         F: FnOnce(OverlayUI) + Send + 'static,
     {
         let window_weak = window_weak.clone();
-        slint::invoke_from_event_loop(move || {
+        invoke_on_ui_thread(move || {
             if let Some(window) = window_weak.upgrade() {
                 action(window);
             }
@@ -247,13 +2257,15 @@ pub fn get_overlay_manager() -> &'static Mutex<OverlayManager> {
 }
 
 pub fn create_text_overlay(text: &str, x: i32, y: i32, width: i32, height: i32) -> Result<OverlayId, OverlayError> {
-    let manager = get_overlay_manager().lock().map_err(|_| OverlayError::LockError)?;
+    // A clone, so the global lock isn't held while creation waits for the UI thread
+    let manager = get_overlay_manager().lock().map_err(|_| OverlayError::LockError)?.clone();
 
     let text_config = TextConfig {
         content: text.to_string(),
         font_size: 24.0,
         color: "#FFFFFFFF".to_string(),
         position: (x, y),
+        ..Default::default()
     };
 
     let overlay_config = OverlayConfig {
@@ -263,6 +2275,7 @@ pub fn create_text_overlay(text: &str, x: i32, y: i32, width: i32, height: i32)
         transparent: true,
         always_on_top: true,
         ignore_input: true,
+        ..Default::default()
     };
 
     let overlay_id = manager.create_overlay(overlay_config)?;
@@ -277,7 +2290,7 @@ pub fn update_overlay_text(overlay_id: &OverlayId, text: &str) -> Result<(), Ove
     manager.update_text(overlay_id, text)?;
 
     if let Err(e) = manager.show_overlay(overlay_id) {
-        log::warn!("Could not show overlay after text update: {}", e);
+        overlay_log!(warn, overlay_id, "Could not show overlay after text update: {}", e);
     }
 
     Ok(())
@@ -287,3 +2300,485 @@ pub fn remove_overlay(overlay_id: &OverlayId) -> Result<(), OverlayError> {
     let manager = get_overlay_manager().lock().map_err(|_| OverlayError::LockError)?;
     manager.remove_overlay(overlay_id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_font_size_clamping() {
+        assert_eq!(clamp_font_size(0.0), TextConfig::MIN_FONT_SIZE);
+        assert_eq!(clamp_font_size(10000.0), TextConfig::MAX_FONT_SIZE);
+        assert_eq!(clamp_font_size(-5.0), TextConfig::MIN_FONT_SIZE);
+        assert_eq!(clamp_font_size(24.0), 24.0);
+        assert_eq!(clamp_font_size(f32::NAN), 24.0);
+    }
+
+    #[test]
+    fn test_truncate_text() {
+        let long = "a".repeat(10_000);
+        let truncated = truncate_text(&long, 100);
+        assert_eq!(truncated.chars().count(), 100);
+        assert!(truncated.ends_with("a…"));
+
+        assert_eq!(truncate_text("short", 100), "short");
+        assert_eq!(truncate_text("exact", 5), "exact");
+        // Combining marks stay attached to their base character
+        assert_eq!(truncate_text("e\u{301}e\u{301}e\u{301}", 2), "e\u{301}…");
+    }
+
+    #[test]
+    fn test_truncate_text_front() {
+        assert_eq!(truncate_text_front("one two three", 9), "…wo three");
+        assert_eq!(truncate_text_front("short", 5), "short");
+        assert_eq!(truncate_text_front("short", 0), "");
+        assert_eq!(truncate_text_front("e\u{301}e\u{301}e\u{301}", 2), "…e\u{301}");
+
+        // Appending to an already cut line keeps a single marker
+        let appended = format!("{}{}", truncate_text_front("one two three", 9), " four");
+        assert_eq!(truncate_text_front(&appended, 9), "…ree four");
+    }
+
+    #[test]
+    fn test_scale_factor_validation() {
+        let manager = OverlayManager::new();
+        let id = "missing".to_string();
+        for factor in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert!(matches!(manager.set_scale_factor(&id, factor), Err(OverlayError::InvalidScaleFactor(_))));
+        }
+        assert!(matches!(manager.set_scale_factor(&id, 1.0), Err(OverlayError::OverlayNotFound(_))));
+    }
+
+    #[test]
+    fn test_applied_props_skip_unchanged_values() {
+        let mut applied = AppliedProps::default();
+        assert!(AppliedProps::changed(&mut applied.size, (300, 100)));
+        assert!(AppliedProps::changed(&mut applied.font_size, 24.0));
+        let snapshot = applied.clone();
+
+        // Re-applying the same values is a no-op and leaves the state untouched
+        assert!(!AppliedProps::changed(&mut applied.size, (300, 100)));
+        assert!(!AppliedProps::changed(&mut applied.font_size, 24.0));
+        assert_eq!(applied, snapshot);
+
+        assert!(AppliedProps::changed(&mut applied.size, (640, 100)));
+        assert_eq!(applied.size, Some((640, 100)));
+    }
+
+    #[test]
+    fn test_presentation_backup_round_trip() {
+        let original = OverlayConfig {
+            ignore_input: false,
+            always_on_top: false,
+            locked: false,
+            ..Default::default()
+        };
+        let mut config = original.clone();
+
+        let backup = PresentationBackup::force(&mut config);
+        assert!(config.ignore_input && config.always_on_top && config.locked);
+
+        backup.restore(&mut config);
+        assert_eq!(
+            (config.ignore_input, config.always_on_top, config.locked),
+            (original.ignore_input, original.always_on_top, original.locked)
+        );
+    }
+
+    #[test]
+    fn test_quick_config_uses_defaults() {
+        let manager = OverlayManager::new();
+        manager
+            .set_defaults(OverlayConfig {
+                width: 640,
+                height: 90,
+                always_on_top: false,
+                ..Default::default()
+            })
+            .unwrap();
+
+        let config = manager.quick_config("hello", 10, 20).unwrap();
+        assert_eq!(config.text.content, "hello");
+        assert_eq!(config.text.position, (10, 20));
+        assert_eq!((config.width, config.height), (640, 90));
+        assert!(!config.always_on_top);
+    }
+
+    /// Registers an overlay without a window, as `create_overlay` would on a headless run
+    fn insert_headless(manager: &OverlayManager, config: OverlayConfig) -> OverlayId {
+        let overlay_id = Uuid::new_v4().to_string();
+        let overlay = OverlayWindow {
+            window_weak: Weak::default(),
+            config,
+            created_on: std::thread::current().id(),
+            sequence: manager.next_sequence.fetch_add(1, Ordering::Relaxed),
+            applied: Arc::new(Mutex::new(AppliedProps::default())),
+        };
+        manager.overlays.lock().unwrap().insert(overlay_id.clone(), overlay);
+        overlay_id
+    }
+
+    #[test]
+    fn test_prune_dead_cleans_up_like_remove() {
+        let manager = OverlayManager::new();
+        let parent = insert_headless(&manager, OverlayConfig::default());
+        let child = insert_headless(&manager, OverlayConfig::default());
+        manager.links.lock().unwrap().link(&child, &parent, (0, 10)).unwrap();
+        let removed = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&removed);
+        manager
+            .set_observer(Box::new(move |event| {
+                if matches!(event, OverlayEvent::Removed { .. }) {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+            }))
+            .unwrap();
+
+        // Headless overlays have no window, so both count as dead
+        assert_eq!(manager.prune_dead().unwrap(), 2);
+        assert!(manager.list_overlays_ordered().unwrap().is_empty());
+        assert!(manager.links.lock().unwrap().children(&parent).is_empty());
+        assert_eq!(removed.load(Ordering::Relaxed), 2);
+    }
+
+    /// Keeps every log record as `(target, message)`
+    struct CapturingLogger(Mutex<Vec<(String, String)>>);
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            let entry = (record.target().to_string(), record.args().to_string());
+            self.0.lock().unwrap().push(entry);
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CapturingLogger = CapturingLogger(Mutex::new(Vec::new()));
+
+    #[test]
+    fn test_overlay_logs_use_its_target() {
+        let _ = log::set_logger(&LOGGER);
+        log::set_max_level(log::LevelFilter::Debug);
+
+        let manager = OverlayManager::new();
+        let overlay_id = insert_headless(&manager, OverlayConfig::default());
+        // Releasing the window needs the event loop, but the removal is logged first
+        let _ = manager.remove_overlay(&overlay_id);
+
+        let target = format!("subs_overlay::overlay::{}", &overlay_id[..8]);
+        let records = LOGGER.0.lock().unwrap();
+        assert!(records.iter().any(|(t, message)| *t == target && message.contains("Removed overlay")));
+    }
+
+    #[test]
+    fn test_overlay_limit_boundary() {
+        let manager = OverlayManager::new();
+        assert!(manager.check_overlay_limit().is_ok());
+
+        manager.set_max_overlays(2);
+        insert_headless(&manager, OverlayConfig::default());
+        assert!(manager.check_overlay_limit().is_ok());
+        insert_headless(&manager, OverlayConfig::default());
+        assert!(matches!(manager.check_overlay_limit(), Err(OverlayError::LimitExceeded(2))));
+
+        manager.set_max_overlays(0);
+        assert!(matches!(manager.check_overlay_limit(), Err(OverlayError::LimitExceeded(0))));
+    }
+
+    #[test]
+    fn test_unknown_id_is_not_found() {
+        let manager = OverlayManager::new();
+        let bogus = "no-such-overlay".to_string();
+        let not_found = |result: Result<(), OverlayError>| matches!(result, Err(OverlayError::OverlayNotFound(id)) if id == bogus);
+
+        assert!(not_found(manager.update_text(&bogus, "Hola")));
+        assert!(not_found(manager.update_position(&bogus, 10, 20)));
+        assert!(not_found(manager.hide_overlay(&bogus)));
+        assert!(not_found(manager.show_overlay(&bogus)));
+        assert!(not_found(manager.update_config(&bogus, OverlayConfig::default())));
+        assert!(not_found(manager.request_attention(&bogus, window_manager::AttentionMode::Count(1))));
+        assert!(not_found(update_overlay_text(&bogus, "Hola")));
+    }
+
+    #[test]
+    fn test_config_from_clipboard_json() {
+        let config = OverlayConfig {
+            text: TextConfig {
+                content: "Hola".to_string(),
+                position: (40, 900),
+                ..Default::default()
+            },
+            rotation_deg: 15.0,
+            ..Default::default()
+        };
+        let json = serde_json::to_string_pretty(&config).unwrap();
+
+        let pasted = config_from_json(&format!("\n{}\n", json)).unwrap();
+        assert_eq!(pasted.text.content, "Hola");
+        assert_eq!(pasted.text.position, (40, 900));
+        assert_eq!(pasted.rotation_deg, 15.0);
+
+        assert!(matches!(config_from_json("just some text"), Err(OverlayError::Clipboard(_))));
+        assert!(matches!(config_from_json(""), Err(OverlayError::Clipboard(_))));
+    }
+
+    #[test]
+    fn test_rendered_colors_resolve_named_colors() {
+        let manager = OverlayManager::new();
+        let mut config = OverlayConfig::default();
+        config.text.color = "red".to_string();
+        let overlay_id = insert_headless(&manager, config);
+
+        let rendered = manager.rendered_colors(&overlay_id).unwrap();
+        assert_eq!(rendered.text, 0xFFFF0000);
+        assert_eq!(rendered.background, None);
+
+        lock_applied(&manager.overlays.lock().unwrap()[&overlay_id].applied).color = Some(0x80FFFFFF);
+        assert_eq!(manager.rendered_colors(&overlay_id).unwrap().text, 0x80FFFFFF);
+    }
+
+    #[test]
+    fn test_grid_position() {
+        let manager = OverlayManager::new();
+        assert_eq!(manager.grid_position((103, 97)), (103, 97));
+
+        manager.set_grid(10);
+        assert_eq!(manager.grid_position((103, 97)), (100, 100));
+
+        manager.set_grid(0);
+        assert_eq!(manager.grid_position((103, 97)), (103, 97));
+    }
+
+    #[test]
+    fn test_text_fits_box() {
+        assert!(text_fits_box((292.0, 92.0), (300.0, 100.0), 4.0));
+        assert!(!text_fits_box((293.0, 40.0), (300.0, 100.0), 4.0));
+        assert!(!text_fits_box((100.0, 93.0), (300.0, 100.0), 4.0));
+        assert!(text_fits_box((300.0, 100.0), (300.0, 100.0), 0.0));
+    }
+
+    #[test]
+    fn test_owner_thread_is_first_claimer() {
+        let owner = Arc::new(OwnerThread::new());
+        // Asking doesn't claim
+        assert!(!owner.is_current());
+        owner.claim();
+        assert!(owner.is_current());
+
+        let other = Arc::clone(&owner);
+        assert!(!std::thread::spawn(move || {
+            other.claim();
+            other.is_current()
+        })
+        .join()
+        .unwrap());
+    }
+
+    #[test]
+    fn test_stacking_order_by_priority() {
+        let entries = vec![(5, 0, "alert"), (0, 1, "captions"), (0, 2, "chat"), (-1, 3, "background")];
+        assert_eq!(stacking_order(entries), vec!["background", "captions", "chat", "alert"]);
+    }
+
+    #[test]
+    fn test_list_overlays_ordered() {
+        let manager = OverlayManager::new();
+        let created: Vec<OverlayId> = (0..3)
+            .map(|_| insert_headless(&manager, OverlayConfig::default()))
+            .collect();
+
+        assert_eq!(manager.list_overlays_ordered().unwrap(), created);
+    }
+
+    #[test]
+    fn test_observer_receives_events() {
+        let manager = OverlayManager::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        manager
+            .set_observer(Box::new(move |event| sink.lock().unwrap().push(event.clone())))
+            .unwrap();
+
+        manager.notify(OverlayEvent::Removed { id: "a".to_string() });
+        manager.clear_observer().unwrap();
+        manager.notify(OverlayEvent::Removed { id: "b".to_string() });
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(
+            serde_json::to_string(&seen[0]).unwrap(),
+            r#"{"type":"removed","id":"a"}"#
+        );
+    }
+
+    #[test]
+    fn test_observer_can_call_back_into_manager() {
+        let manager = OverlayManager::new();
+        let handle = manager.clone();
+        // Would deadlock if the observer ran with its own lock held
+        manager
+            .set_observer(Box::new(move |_| handle.clear_observer().unwrap()))
+            .unwrap();
+        manager.notify(OverlayEvent::Removed { id: "a".to_string() });
+        assert!(manager.observer.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_drop_shadow_round_trip() {
+        let config = OverlayConfig {
+            text: TextConfig {
+                drop_shadow: true,
+                shadow_offset: (1.5, 3.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let parsed: OverlayConfig = serde_json::from_str(&json).unwrap();
+        assert!(parsed.text.drop_shadow);
+        assert_eq!(parsed.text.shadow_offset, (1.5, 3.0));
+    }
+
+    #[test]
+    fn test_padding_round_trip() {
+        let mut config = OverlayConfig {
+            padding: 12.5,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let parsed: OverlayConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.padding, 12.5);
+
+        config.padding = -3.0;
+        config.validate();
+        assert_eq!(config.padding, 0.0);
+        config.padding = f32::NAN;
+        config.validate();
+        assert_eq!(config.padding, 4.0);
+    }
+
+    #[test]
+    fn test_background_image_round_trip() {
+        let config = OverlayConfig {
+            background_image: Some("card.png".to_string()),
+            background_fit: BackgroundFit::Contain,
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["background_fit"], "contain");
+        let parsed: OverlayConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.background_image.as_deref(), Some("card.png"));
+        assert_eq!(parsed.background_fit, BackgroundFit::Contain);
+    }
+
+    #[test]
+    fn test_rotation_round_trip_and_bounds() {
+        let mut config = OverlayConfig {
+            width: 200,
+            height: 50,
+            rotation_deg: -90.0,
+            ..Default::default()
+        };
+        config.validate();
+        assert_eq!(config.rotation_deg, 270.0);
+        assert_eq!(config.window_size(), (50, 200));
+
+        let json = serde_json::to_string(&config).unwrap();
+        let parsed: OverlayConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.rotation_deg, 270.0);
+
+        config.rotation_deg = 0.0;
+        assert_eq!(config.window_size(), (200, 50));
+        config.width = 100;
+        config.height = 100;
+        config.rotation_deg = 45.0;
+        assert_eq!(config.window_size(), (142, 142));
+
+        assert_eq!(normalize_rotation(f32::NAN), 0.0);
+    }
+
+    #[test]
+    fn test_window_alpha_round_trip() {
+        let config = OverlayConfig {
+            window_alpha: 128,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let parsed: OverlayConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.window_alpha, 128);
+    }
+
+    #[test]
+    fn test_config_saved_before_new_fields() {
+        // Only the fields the first release had
+        let saved = r##"{
+            "text": {"content": "hi", "font_size": 24.0, "color": "#FFFFFF", "position": [0, 0]},
+            "width": 300, "height": 100, "transparent": true, "always_on_top": true, "ignore_input": true
+        }"##;
+        let defaults = [
+            ("/text/drop_shadow", serde_json::json!(false)),
+            ("/text/shadow_offset", serde_json::json!([2.0, 2.0])),
+            ("/locked", serde_json::json!(false)),
+            ("/pixel_snap", serde_json::json!(false)),
+            ("/image", serde_json::json!(null)),
+            ("/hide_when_empty", serde_json::json!(false)),
+            ("/clamp_to_screen", serde_json::json!(false)),
+            ("/window_alpha", serde_json::json!(255)),
+            ("/title", serde_json::json!(null)),
+            ("/max_chars", serde_json::json!(null)),
+            ("/fallback_color", serde_json::json!(null)),
+            ("/position_key", serde_json::json!(null)),
+            ("/opaque_background", serde_json::json!(null)),
+            ("/rotation_deg", serde_json::json!(0.0)),
+            ("/padding", serde_json::json!(4.0)),
+            ("/priority", serde_json::json!(0)),
+            ("/high_quality_text", serde_json::json!(true)),
+            ("/background_image", serde_json::json!(null)),
+            ("/background_fit", serde_json::json!("cover")),
+            ("/wrap_text", serde_json::json!(false)),
+            ("/lines", serde_json::json!([])),
+        ];
+
+        let parsed: OverlayConfig = serde_json::from_str(saved).unwrap();
+        let parsed = serde_json::to_value(parsed).unwrap();
+        for (field, expected) in &defaults {
+            assert_eq!(parsed.pointer(field), Some(expected), "{}", field);
+        }
+
+        // A field added later needs a row above
+        let saved: serde_json::Value = serde_json::from_str(saved).unwrap();
+        for section in ["", "/text"] {
+            for key in parsed.pointer(section).unwrap().as_object().unwrap().keys() {
+                let field = format!("{}/{}", section, key);
+                assert!(
+                    saved.pointer(&field).is_some() || defaults.iter().any(|(known, _)| *known == field),
+                    "{} has no default listed",
+                    field
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_event_loop_not_running() {
+        assert!(matches!(
+            event_loop_error(EventLoopError::NoEventLoopProvider),
+            OverlayError::EventLoopNotRunning
+        ));
+        assert!(matches!(
+            event_loop_error(EventLoopError::EventLoopTerminated),
+            OverlayError::EventLoopNotRunning
+        ));
+
+        // Without a backend (as in headless test runs) scheduling reports the typed error
+        // instead of panicking or being swallowed
+        assert!(matches!(
+            OverlayManager::new().stop_watchdog(),
+            Err(OverlayError::EventLoopNotRunning)
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+use slint::{ModelRc, SharedString, VecModel};
+
+use crate::{clamp_font_size, color_utils, OverlayConfig, OverlayError, OverlayId, OverlayManager, OverlayUI, StyledLineData};
+
+/// One line of a multi-line overlay, with its own style. Unset fields follow the
+/// overlay's text style.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StyledLine {
+    pub text: String,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub font_size: Option<f32>,
+    #[serde(default)]
+    pub alignment: LineAlignment,
+}
+
+/// Horizontal alignment of a `StyledLine` inside the box
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineAlignment {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+impl LineAlignment {
+    /// Value of the `alignment` field of the component's `StyledLineData`
+    fn slint_mode(self) -> i32 {
+        match self {
+            LineAlignment::Left => 0,
+            LineAlignment::Center => 1,
+            LineAlignment::Right => 2,
+        }
+    }
+}
+
+/// Color and font size a line is drawn with
+fn resolve_line(line: &StyledLine, config: &OverlayConfig) -> (u32, f32) {
+    let argb = match &line.color {
+        Some(color) => color_utils::resolve_color(color, config.fallback_color.as_deref()),
+        None => config.text_color_argb(),
+    };
+    (argb, line.font_size.unwrap_or(config.text.font_size))
+}
+
+/// Shows `config.lines` stacked in the box, or the plain text when there are none
+pub(crate) fn apply_lines(window: &OverlayUI, config: &OverlayConfig) {
+    let lines: Vec<StyledLineData> = config
+        .lines
+        .iter()
+        .map(|line| {
+            let (argb, font_size) = resolve_line(line, config);
+            StyledLineData {
+                text: SharedString::from(line.text.as_str()),
+                color: slint::Brush::from(slint::Color::from_argb_encoded(argb)),
+                font_size,
+                alignment: line.alignment.slint_mode(),
+            }
+        })
+        .collect();
+    window.set_lines_active(!lines.is_empty());
+    window.set_lines(ModelRc::new(VecModel::from(lines)));
+}
+
+impl OverlayManager {
+    /// Shows `lines` stacked top to bottom in one overlay, each with its own color, size and
+    /// alignment, e.g. a large title above a smaller subtitle. The overlay's text becomes the
+    /// lines joined by `\n`; a later `update_text` switches back to a single plain text.
+    pub fn set_lines(&self, overlay_id: &OverlayId, lines: Vec<StyledLine>) -> Result<(), OverlayError> {
+        let mut lines = lines;
+        for line in &mut lines {
+            if let Some(color) = &line.color {
+                if color_utils::parse_argb(color).is_none() {
+                    return Err(OverlayError::InvalidColor(color.clone()));
+                }
+            }
+            line.font_size = line.font_size.map(clamp_font_size);
+        }
+        let text = lines.iter().map(|line| line.text.as_str()).collect::<Vec<_>>().join("\n");
+        // Replaces the text (and anything animating it) first, then draws it as lines
+        self.replace_text(overlay_id, true, |_| text)?;
+
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get_mut(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+        overlay.config.lines = lines;
+        let config = overlay.config.clone();
+        self.execute_ui_action(&overlay.window_weak, move |window| apply_lines(&window, &config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_inherit_text_style() {
+        let mut config = OverlayConfig::default();
+        config.text.color = "#FFFFFFFF".to_string();
+        config.text.font_size = 20.0;
+
+        let title = StyledLine {
+            text: "Title".to_string(),
+            color: Some("#FFFF0000".to_string()),
+            font_size: Some(36.0),
+            alignment: LineAlignment::Left,
+        };
+        let subtitle = StyledLine {
+            text: "Subtitle".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(resolve_line(&title, &config), (0xFFFF0000, 36.0));
+        assert_eq!(resolve_line(&subtitle, &config), (0xFFFFFFFF, 20.0));
+        assert_eq!([title.alignment, subtitle.alignment].map(LineAlignment::slint_mode), [0, 1]);
+    }
+
+    #[test]
+    fn test_lines_round_trip() {
+        let config = OverlayConfig {
+            lines: vec![
+                StyledLine {
+                    text: "Title".to_string(),
+                    font_size: Some(36.0),
+                    alignment: LineAlignment::Right,
+                    ..Default::default()
+                },
+                StyledLine {
+                    text: "Subtitle".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["lines"][0]["alignment"], "right");
+        let parsed: OverlayConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.lines, config.lines);
+
+        // Only the text is required per line
+        let line: StyledLine = serde_json::from_str(r#"{"text": "Hola"}"#).unwrap();
+        assert_eq!(
+            line,
+            StyledLine {
+                text: "Hola".to_string(),
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use crate::{OverlayError, OverlayId, OverlayManager};
+
+/// Child -> (parent, offset) links between overlays; always acyclic
+#[derive(Debug, Default)]
+pub(crate) struct OverlayLinks {
+    parents: HashMap<OverlayId, (OverlayId, (i32, i32))>,
+}
+
+impl OverlayLinks {
+    /// Links `child` to `parent`, replacing any previous parent. Fails if `parent` is
+    /// `child` itself or already follows it, directly or through other links.
+    pub(crate) fn link(&mut self, child: &str, parent: &str, offset: (i32, i32)) -> Result<(), OverlayError> {
+        let mut ancestor = Some(parent);
+        while let Some(id) = ancestor {
+            if id == child {
+                return Err(OverlayError::LinkCycle(child.to_string(), parent.to_string()));
+            }
+            ancestor = self.parents.get(id).map(|(parent, _)| parent.as_str());
+        }
+        self.parents.insert(child.to_string(), (parent.to_string(), offset));
+        Ok(())
+    }
+
+    pub(crate) fn unlink(&mut self, child: &str) -> bool {
+        self.parents.remove(child).is_some()
+    }
+
+    /// Direct children of `parent` with their offsets
+    pub(crate) fn children(&self, parent: &str) -> Vec<(OverlayId, (i32, i32))> {
+        self.parents
+            .iter()
+            .filter(|(_, (p, _))| p == parent)
+            .map(|(child, (_, offset))| (child.clone(), *offset))
+            .collect()
+    }
+
+    /// Drops every link `overlay_id` takes part in, as child or parent
+    pub(crate) fn forget(&mut self, overlay_id: &str) {
+        self.parents
+            .retain(|child, (parent, _)| child != overlay_id && parent != overlay_id);
+    }
+}
+
+impl OverlayManager {
+    /// Makes `child_id` follow `parent_id`: whenever the parent moves, is shown or is
+    /// hidden, the child does the same, staying at `offset` from the parent's position.
+    /// The child is moved into place right away. Linking an overlay to itself or to one
+    /// of its own followers fails with `LinkCycle`.
+    pub fn link(&self, child_id: &OverlayId, parent_id: &OverlayId, offset: (i32, i32)) -> Result<(), OverlayError> {
+        let parent_position = {
+            let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+            if !overlays.contains_key(child_id) {
+                return Err(OverlayError::OverlayNotFound(child_id.clone()));
+            }
+            overlays
+                .get(parent_id)
+                .ok_or_else(|| OverlayError::OverlayNotFound(parent_id.clone()))?
+                .config
+                .text
+                .position
+        };
+        self.links
+            .lock()
+            .map_err(|_| OverlayError::LockError)?
+            .link(child_id, parent_id, offset)?;
+
+        let (x, y) = parent_position;
+        self.update_position(child_id, x.saturating_add(offset.0), y.saturating_add(offset.1))
+    }
+
+    /// Stops `child_id` following its parent; returns whether it was linked
+    pub fn unlink(&self, child_id: &OverlayId) -> Result<bool, OverlayError> {
+        Ok(self.links.lock().map_err(|_| OverlayError::LockError)?.unlink(child_id))
+    }
+
+    /// Moves the children of an overlay that just moved to `(x, y)`
+    pub(crate) fn move_linked_children(&self, parent_id: &OverlayId, (x, y): (i32, i32)) -> Result<(), OverlayError> {
+        let children = self.links.lock().map_err(|_| OverlayError::LockError)?.children(parent_id);
+        for (child_id, (dx, dy)) in children {
+            let target = (x.saturating_add(dx), y.saturating_add(dy));
+            if let Err(e) = self.move_overlay(&child_id, |_| target) {
+                overlay_log!(warn, &child_id, "Could not move {} along with {}: {}", child_id, parent_id, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Shows or hides the children of an overlay that was just shown or hidden
+    pub(crate) fn apply_visibility_to_children(&self, parent_id: &OverlayId, visible: bool) -> Result<(), OverlayError> {
+        let children = self.links.lock().map_err(|_| OverlayError::LockError)?.children(parent_id);
+        for (child_id, _) in children {
+            let result = if visible {
+                self.apply_show(&child_id)
+            } else {
+                self.apply_hide(&child_id)
+            };
+            if let Err(e) = result {
+                overlay_log!(
+                    warn,
+                    &child_id,
+                    "Could not update visibility of {} along with {}: {}",
+                    child_id,
+                    parent_id,
+                    e
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_links_reject_cycles() {
+        let mut links = OverlayLinks::default();
+        links.link("label", "gauge", (0, -30)).unwrap();
+        links.link("gauge", "panel", (10, 10)).unwrap();
+
+        assert!(matches!(links.link("panel", "label", (0, 0)), Err(OverlayError::LinkCycle(..))));
+        assert!(matches!(links.link("panel", "panel", (0, 0)), Err(OverlayError::LinkCycle(..))));
+        assert_eq!(links.children("gauge"), vec![("label".to_string(), (0, -30))]);
+
+        // Relinking replaces the previous parent
+        links.link("label", "panel", (5, 5)).unwrap();
+        assert!(links.children("gauge").is_empty());
+
+        links.forget("panel");
+        assert!(links.children("panel").is_empty());
+        assert!(!links.unlink("gauge"));
+    }
+}
@@ -0,0 +1,32 @@
+//! Per-overlay log targets.
+//!
+//! Messages about one overlay are logged under `subs_overlay::overlay::<short id>`, the first
+//! eight characters of its id, so `RUST_LOG=subs_overlay::overlay::1a2b3c4d=debug` follows a
+//! single overlay and `RUST_LOG=subs_overlay::overlay=warn` covers all of them.
+
+/// Log target for messages about `overlay_id`
+pub(crate) fn log_target(overlay_id: &str) -> String {
+    format!("subs_overlay::overlay::{}", overlay_id.get(..8).unwrap_or(overlay_id))
+}
+
+/// `log::<level>!` under an overlay's target, e.g. `overlay_log!(warn, &overlay_id, "Could not hide: {}", e)`
+macro_rules! overlay_log {
+    ($level:ident, $overlay_id:expr, $($arg:tt)+) => {
+        log::$level!(target: &$crate::logging::log_target($overlay_id), $($arg)+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_target_uses_short_id() {
+        assert_eq!(
+            log_target("1a2b3c4d-0000-4000-8000-000000000000"),
+            "subs_overlay::overlay::1a2b3c4d"
+        );
+        // Ids shorter than the short form are used whole
+        assert_eq!(log_target("hud"), "subs_overlay::overlay::hud");
+    }
+}
@@ -2,12 +2,61 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use std::error::Error;
-use subs_overlay_lib::{create_text_overlay, remove_overlay, update_overlay_text};
+use std::io::BufRead;
+use subs_overlay_lib::{create_text_overlay, init_ui_thread, remove_overlay, update_overlay_text};
 
 use log::{error, info};
 
 fn main() -> Result<(), Box<dyn Error>> {
     env_logger::init();
+    // The event loop runs on this thread, after the overlay is created
+    init_ui_thread();
+
+    match std::env::args().nth(1).as_deref() {
+        Some("stdin") => run_stdin(),
+        Some(other) => Err(format!("Unknown subcommand: {}. Available: stdin", other).into()),
+        None => run_demo(),
+    }
+}
+
+/// Shows each line read from stdin as the overlay text, e.g. `tail -f log | subs_overlay stdin`
+fn run_stdin() -> Result<(), Box<dyn Error>> {
+    let overlay_id = create_text_overlay("", 200, 200, 800, 100)?;
+    info!("Overlay created with ID: {}. Reading lines from stdin...", overlay_id);
+
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    error!("Error reading stdin: {}", e);
+                    break;
+                }
+            };
+
+            // An empty line clears the overlay
+            if let Err(e) = update_overlay_text(&overlay_id, line.trim_end()) {
+                error!("Error updating text: {}", e);
+            }
+        }
+
+        info!("End of input. Removing overlay...");
+        if let Err(e) = remove_overlay(&overlay_id) {
+            error!("Error removing overlay: {}", e);
+        }
+
+        if let Err(e) = slint::quit_event_loop() {
+            eprintln!("Error quitting event loop: {}", e);
+        }
+    });
+
+    slint::run_event_loop()?;
+
+    Ok(())
+}
+
+/// Shows a counter that updates every second for 30 seconds
+fn run_demo() -> Result<(), Box<dyn Error>> {
     info!("Creating a transparent overlay...");
 
     // Create a simple text overlay using the convenience function
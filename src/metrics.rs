@@ -0,0 +1,92 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Snapshot of the manager's activity counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Metrics {
+    pub overlays_created: u64,
+    pub overlays_removed: u64,
+    pub text_updates: u64,
+    pub api_requests: u64,
+    pub mcp_calls: u64,
+}
+
+/// Lock-free counters backing `Metrics`
+#[derive(Debug, Default)]
+pub(crate) struct MetricsCounters {
+    pub(crate) overlays_created: AtomicU64,
+    pub(crate) overlays_removed: AtomicU64,
+    pub(crate) text_updates: AtomicU64,
+    pub(crate) api_requests: AtomicU64,
+    pub(crate) mcp_calls: AtomicU64,
+}
+
+impl MetricsCounters {
+    pub(crate) fn incr(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> Metrics {
+        Metrics {
+            overlays_created: self.overlays_created.load(Ordering::Relaxed),
+            overlays_removed: self.overlays_removed.load(Ordering::Relaxed),
+            text_updates: self.text_updates.load(Ordering::Relaxed),
+            api_requests: self.api_requests.load(Ordering::Relaxed),
+            mcp_calls: self.mcp_calls.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Metrics {
+    /// Renders the counters in the Prometheus text exposition format
+    pub fn to_prometheus(&self, active_overlays: usize) -> String {
+        let counters = [
+            ("overlays_created_total", "Overlays created since startup", self.overlays_created),
+            ("overlays_removed_total", "Overlays removed since startup", self.overlays_removed),
+            ("text_updates_total", "Overlay text updates since startup", self.text_updates),
+            ("api_requests_total", "API requests handled since startup", self.api_requests),
+            ("mcp_calls_total", "MCP calls handled since startup", self.mcp_calls),
+        ];
+
+        let mut out = String::new();
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP subs_overlay_{} {}", name, help);
+            let _ = writeln!(out, "# TYPE subs_overlay_{} counter", name);
+            let _ = writeln!(out, "subs_overlay_{} {}", name, value);
+        }
+        let _ = writeln!(out, "# HELP subs_overlay_active_overlays Overlays currently registered");
+        let _ = writeln!(out, "# TYPE subs_overlay_active_overlays gauge");
+        let _ = writeln!(out, "subs_overlay_active_overlays {}", active_overlays);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters_snapshot() {
+        let counters = MetricsCounters::default();
+        MetricsCounters::incr(&counters.overlays_created);
+        MetricsCounters::incr(&counters.overlays_created);
+        MetricsCounters::incr(&counters.text_updates);
+
+        let metrics = counters.snapshot();
+        assert_eq!(metrics.overlays_created, 2);
+        assert_eq!(metrics.text_updates, 1);
+        assert_eq!(metrics.overlays_removed, 0);
+    }
+
+    #[test]
+    fn test_prometheus_format() {
+        let metrics = Metrics {
+            overlays_created: 3,
+            ..Default::default()
+        };
+        let text = metrics.to_prometheus(2);
+        assert!(text.contains("# TYPE subs_overlay_overlays_created_total counter\n"));
+        assert!(text.contains("subs_overlay_overlays_created_total 3\n"));
+        assert!(text.contains("subs_overlay_active_overlays 2\n"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Open Sound Control input, enabled with the `osc` feature.
+//!
+//! Address space (`<id>` is the overlay id returned by `create_overlay`):
+//!
+//! - `/overlay/<id>/text` with one string argument replaces the overlay text
+//! - `/overlay/<id>/position` with two numeric arguments (x, y) moves the overlay
+//!
+//! Float arguments are rounded to whole pixels with `window_manager::px_round`. Bundles are unpacked recursively.
+
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::thread::JoinHandle;
+
+use rosc::{OscMessage, OscPacket, OscType};
+
+use crate::window_manager::px_round;
+use crate::{OverlayError, OverlayId, OverlayManager};
+
+#[derive(Debug, Clone, PartialEq)]
+enum OscCommand {
+    Text(String),
+    Position(i32, i32),
+}
+
+fn arg_as_i32(arg: &OscType) -> Option<i32> {
+    match arg {
+        OscType::Int(value) => Some(*value),
+        OscType::Long(value) => i32::try_from(*value).ok(),
+        OscType::Float(value) => Some(px_round(*value as f64)),
+        OscType::Double(value) => Some(px_round(*value)),
+        _ => None,
+    }
+}
+
+fn parse_message(message: &OscMessage) -> Option<(OverlayId, OscCommand)> {
+    let mut parts = message.addr.strip_prefix("/overlay/")?.splitn(2, '/');
+    let id = parts.next().filter(|id| !id.is_empty())?.to_string();
+
+    let command = match (parts.next()?, message.args.as_slice()) {
+        ("text", [OscType::String(text)]) => OscCommand::Text(text.clone()),
+        ("position", [x, y]) => OscCommand::Position(arg_as_i32(x)?, arg_as_i32(y)?),
+        _ => return None,
+    };
+
+    Some((id, command))
+}
+
+fn apply_packet(manager: &OverlayManager, packet: OscPacket) {
+    match packet {
+        OscPacket::Message(message) => {
+            let Some((id, command)) = parse_message(&message) else {
+                log::warn!("Ignoring unsupported OSC message: {} {:?}", message.addr, message.args);
+                return;
+            };
+
+            let result = match command {
+                OscCommand::Text(text) => manager.update_text(&id, &text),
+                OscCommand::Position(x, y) => manager.update_position(&id, x, y),
+            };
+            if let Err(e) = result {
+                log::warn!("Could not apply OSC message {}: {}", message.addr, e);
+            }
+        }
+        OscPacket::Bundle(bundle) => {
+            for packet in bundle.content {
+                apply_packet(manager, packet);
+            }
+        }
+    }
+}
+
+impl OverlayManager {
+    /// Listens for OSC messages on `addr` (UDP) and applies them to the managed overlays.
+    ///
+    /// The socket is bound before returning, so bind errors are reported to the caller.
+    pub fn serve_osc<A: ToSocketAddrs>(&self, addr: A) -> Result<JoinHandle<()>, OverlayError> {
+        let socket = UdpSocket::bind(addr)?;
+        log::info!("Listening for OSC messages on {}", socket.local_addr()?);
+
+        let manager = self.clone();
+        let handle = std::thread::spawn(move || {
+            let mut buf = [0u8; rosc::decoder::MTU];
+            loop {
+                let size = match socket.recv(&mut buf) {
+                    Ok(size) => size,
+                    Err(e) => {
+                        log::error!("OSC socket error: {}", e);
+                        break;
+                    }
+                };
+
+                match rosc::decoder::decode_udp(&buf[..size]) {
+                    Ok((_, packet)) => apply_packet(&manager, packet),
+                    Err(e) => log::warn!("Invalid OSC packet: {:?}", e),
+                }
+            }
+        });
+
+        Ok(handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(addr: &str, args: Vec<OscType>) -> OscMessage {
+        OscMessage {
+            addr: addr.to_string(),
+            args,
+        }
+    }
+
+    #[test]
+    fn test_parse_text() {
+        let msg = message("/overlay/abc/text", vec![OscType::String("hello".to_string())]);
+        assert_eq!(
+            parse_message(&msg),
+            Some(("abc".to_string(), OscCommand::Text("hello".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_parse_position() {
+        let msg = message("/overlay/abc/position", vec![OscType::Int(10), OscType::Float(20.6)]);
+        assert_eq!(parse_message(&msg), Some(("abc".to_string(), OscCommand::Position(10, 21))));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown() {
+        assert_eq!(parse_message(&message("/overlay/abc/color", vec![])), None);
+        assert_eq!(parse_message(&message("/overlay//text", vec![OscType::String("x".into())])), None);
+        assert_eq!(parse_message(&message("/overlay/abc/position", vec![OscType::Int(1)])), None);
+    }
+}
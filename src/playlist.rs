@@ -0,0 +1,147 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::tasks::{self, TaskKind};
+use crate::{OverlayError, OverlayId, OverlayManager};
+
+/// Items of a running playlist and which one is shown
+#[derive(Debug, Clone, PartialEq)]
+struct PlaylistState {
+    items: Vec<String>,
+    index: usize,
+    looping: bool,
+}
+
+impl PlaylistState {
+    /// Moves `step` items forward (or back, if negative) and returns the item now shown.
+    /// Past either end it wraps around when looping and stays put (`None`) otherwise.
+    fn advance(&mut self, step: isize) -> Option<&str> {
+        let len = self.items.len() as isize;
+        let target = self.index as isize + step;
+        let index = if self.looping {
+            target.rem_euclid(len)
+        } else if (0..len).contains(&target) {
+            target
+        } else {
+            return None;
+        };
+        self.index = index as usize;
+        Some(&self.items[self.index])
+    }
+}
+
+type Playlist = (slint::Timer, Rc<RefCell<PlaylistState>>);
+
+impl OverlayManager {
+    /// Cycles the overlay text through `items`, one every `interval`, for rotating
+    /// announcements. The first item shows right away; after the last one the playlist
+    /// starts over when `looping` and otherwise stops on it. Any manual text update
+    /// (`update_text`, `append_text`, `clear_text`) stops the playlist, as does starting another.
+    pub fn start_playlist(
+        &self,
+        overlay_id: &OverlayId,
+        items: Vec<String>,
+        interval: Duration,
+        looping: bool,
+    ) -> Result<(), OverlayError> {
+        self.ensure_exists(overlay_id)?;
+        // Replaces the text and cancels a previous playlist before the new one registers
+        self.update_text(overlay_id, items.first().map(String::as_str).unwrap_or_default())?;
+        if items.len() < 2 {
+            return Ok(());
+        }
+
+        let manager = self.clone();
+        let id = overlay_id.clone();
+        crate::invoke_on_ui_thread(move || {
+            let state = Rc::new(RefCell::new(PlaylistState { items, index: 0, looping }));
+            let tick_state = Rc::clone(&state);
+            let tick_id = id.clone();
+            let timer = slint::Timer::default();
+            timer.start(slint::TimerMode::Repeated, interval, move || {
+                let next = tick_state.borrow_mut().advance(1).map(str::to_string);
+                match next {
+                    Some(text) => {
+                        if let Err(e) = manager.set_text(&tick_id, &text, false) {
+                            overlay_log!(warn, &tick_id, "Could not show next playlist item on {}: {}", tick_id, e);
+                        }
+                    }
+                    // Reached the end without looping; the last item stays
+                    None => {
+                        tasks::with_task(&tick_id, TaskKind::Playlist, |(timer, _): &Playlist| timer.stop());
+                    }
+                }
+            });
+            tasks::register(&id, TaskKind::Playlist, (timer, state));
+        })
+    }
+
+    /// Stops the playlist, leaving the current item on screen
+    pub fn stop_playlist(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        let id = overlay_id.clone();
+        crate::invoke_on_ui_thread(move || tasks::cancel(&id, TaskKind::Playlist))
+    }
+
+    /// Skips to the next playlist item and restarts its interval
+    pub fn next(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        self.step_playlist(overlay_id, 1)
+    }
+
+    /// Goes back to the previous playlist item and restarts its interval
+    pub fn prev(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        self.step_playlist(overlay_id, -1)
+    }
+
+    fn step_playlist(&self, overlay_id: &OverlayId, step: isize) -> Result<(), OverlayError> {
+        let manager = self.clone();
+        let id = overlay_id.clone();
+        crate::invoke_on_ui_thread(move || {
+            // The text is set after the registry is released, since setting it touches tasks too
+            let text = tasks::with_task(&id, TaskKind::Playlist, |(timer, state): &Playlist| {
+                let text = state.borrow_mut().advance(step).map(str::to_string);
+                if text.is_some() {
+                    timer.restart();
+                }
+                text
+            });
+            match text {
+                Some(Some(text)) => {
+                    if let Err(e) = manager.set_text(&id, &text, false) {
+                        overlay_log!(warn, &id, "Could not change playlist item on {}: {}", id, e);
+                    }
+                }
+                Some(None) => {}
+                None => overlay_log!(warn, &id, "Overlay {} has no playlist", id),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playlist(looping: bool) -> PlaylistState {
+        PlaylistState {
+            items: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            index: 0,
+            looping,
+        }
+    }
+
+    #[test]
+    fn test_playlist_advance() {
+        let mut once = playlist(false);
+        assert_eq!(once.advance(1), Some("b"));
+        assert_eq!(once.advance(1), Some("c"));
+        assert_eq!(once.advance(1), None);
+        assert_eq!(once.index, 2);
+        assert_eq!(once.advance(-1), Some("b"));
+
+        let mut looping = playlist(true);
+        assert_eq!(looping.advance(-1), Some("c"));
+        assert_eq!(looping.advance(1), Some("a"));
+        assert_eq!(looping.advance(4), Some("b"));
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::json_file::{load_json, store_json};
+use crate::window_manager::ScreenRect;
+use crate::{OverlayConfig, OverlayError, OverlayId, OverlayManager};
+
+/// File used until `set_position_memory_file` picks another one
+pub(crate) const DEFAULT_POSITION_FILE: &str = "overlay_positions.json";
+
+/// How long moves are collected before the file is written, so a drag doesn't write on every step
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// Rects waiting to be written; `scheduled` while a delayed save is on its way
+#[derive(Debug, Default)]
+struct QueuedRects {
+    rects: HashMap<String, ScreenRect>,
+    scheduled: bool,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct PendingPositions {
+    queue: Mutex<QueuedRects>,
+    /// Held while the file is written, so saves from different threads can't undo each other
+    /// while `queue` stays free for moves and restores
+    write: Mutex<()>,
+}
+
+/// Reads the remembered rects; a missing file (first run) is simply empty
+fn load(path: &Path) -> Result<HashMap<String, ScreenRect>, OverlayError> {
+    load_json(path)
+}
+
+/// Adds `rects` to the file, keeping the other remembered rects
+fn store(path: &Path, rects: &HashMap<String, ScreenRect>) -> Result<(), OverlayError> {
+    let mut positions = load(path)?;
+    positions.extend(rects.iter().map(|(key, rect)| (key.clone(), *rect)));
+    store_json(path, &positions)
+}
+
+impl OverlayManager {
+    /// Changes the JSON file remembered positions are kept in (default `overlay_positions.json`)
+    pub fn set_position_memory_file(&self, path: impl Into<PathBuf>) -> Result<(), OverlayError> {
+        // Queued moves belong to the old file
+        self.save_positions()?;
+        *self.position_file.lock().map_err(|_| OverlayError::LockError)? = path.into();
+        Ok(())
+    }
+
+    /// Remembers the overlay's position and size under `key`, a stable name chosen by the
+    /// caller rather than the random overlay id. The rect is saved now and when the overlay
+    /// is removed, moves are saved in batches shortly after they happen, and it is restored
+    /// when an overlay is created with the same `OverlayConfig::position_key`.
+    pub fn enable_position_memory(&self, overlay_id: &OverlayId, key: &str) -> Result<(), OverlayError> {
+        let rect = {
+            let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+            let overlay = overlays
+                .get_mut(overlay_id)
+                .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+            overlay.config.position_key = Some(key.to_string());
+            stored_rect(&overlay.config)
+        };
+        self.remember_position(key, rect)
+    }
+
+    /// Saves `rect` under `key` right away, along with any queued moves
+    pub(crate) fn remember_position(&self, key: &str, rect: ScreenRect) -> Result<(), OverlayError> {
+        self.pending_positions
+            .queue
+            .lock()
+            .map_err(|_| OverlayError::LockError)?
+            .rects
+            .insert(key.to_string(), rect);
+        self.save_positions()
+    }
+
+    /// Queues `rect` under `key`; every move within `SAVE_DELAY` is written at once, off the
+    /// caller's thread
+    pub(crate) fn queue_position(&self, key: &str, rect: ScreenRect) -> Result<(), OverlayError> {
+        let mut queue = self.pending_positions.queue.lock().map_err(|_| OverlayError::LockError)?;
+        queue.rects.insert(key.to_string(), rect);
+        if !queue.scheduled {
+            queue.scheduled = true;
+            let manager = self.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(SAVE_DELAY);
+                let _ = manager.save_positions();
+            });
+        }
+        Ok(())
+    }
+
+    /// Writes the queued rects, logging instead of failing the caller's operation. Rects stay
+    /// queued until they are on disk, so a failed save is retried by the next one.
+    fn save_positions(&self) -> Result<(), OverlayError> {
+        let _write = self.pending_positions.write.lock().map_err(|_| OverlayError::LockError)?;
+        let rects = {
+            let mut queue = self.pending_positions.queue.lock().map_err(|_| OverlayError::LockError)?;
+            queue.scheduled = false;
+            if queue.rects.is_empty() {
+                return Ok(());
+            }
+            queue.rects.clone()
+        };
+        let path = self.position_file.lock().map_err(|_| OverlayError::LockError)?.clone();
+        match store(&path, &rects) {
+            Ok(()) => {
+                // Keep whatever moved again while the file was being written
+                let mut queue = self.pending_positions.queue.lock().map_err(|_| OverlayError::LockError)?;
+                queue.rects.retain(|key, rect| rects.get(key) != Some(rect));
+            }
+            Err(e) => log::warn!("Could not save overlay positions to {}: {}", path.display(), e),
+        }
+        Ok(())
+    }
+
+    /// Applies the rect remembered for `config.position_key`, if any
+    pub(crate) fn restore_position(&self, config: &mut OverlayConfig) -> Result<(), OverlayError> {
+        let Some(key) = &config.position_key else {
+            return Ok(());
+        };
+        // A move still waiting to be written is the latest one
+        let queued = self.pending_positions.queue.lock().map_err(|_| OverlayError::LockError)?.rects.get(key).copied();
+        let rect = match queued {
+            Some(rect) => Some(rect),
+            None => {
+                let path = self.position_file.lock().map_err(|_| OverlayError::LockError)?.clone();
+                match load(&path) {
+                    Ok(positions) => positions.get(key).copied(),
+                    Err(e) => {
+                        log::warn!("Could not read remembered positions from {}: {}", path.display(), e);
+                        None
+                    }
+                }
+            }
+        };
+        if let Some(rect) = rect {
+            config.text.position = (rect.x, rect.y);
+            config.width = rect.width;
+            config.height = rect.height;
+        }
+        Ok(())
+    }
+}
+
+/// The overlay's rect as stored in its config
+pub(crate) fn stored_rect(config: &OverlayConfig) -> ScreenRect {
+    ScreenRect {
+        x: config.text.position.0,
+        y: config.text.position.1,
+        width: config.width,
+        height: config.height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_memory_round_trip() {
+        let path = std::env::temp_dir().join(format!("subs_overlay_positions_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        // First run: no file yet
+        assert!(load(&path).unwrap().is_empty());
+
+        let rect = ScreenRect { x: 120, y: 900, width: 800, height: 120 };
+        store(&path, &HashMap::from([("captions".to_string(), rect)])).unwrap();
+        let chat = ScreenRect { x: 0, y: 0, width: 300, height: 400 };
+        store(&path, &HashMap::from([("chat".to_string(), chat)])).unwrap();
+
+        let positions = load(&path).unwrap();
+        assert_eq!(positions.get("captions"), Some(&rect));
+        assert_eq!(positions.len(), 2);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
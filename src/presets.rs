@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use slint::ComponentHandle;
+
+use crate::json_file::{load_json, store_json};
+use crate::tasks::{self, TaskKind};
+use crate::{
+    animation, color_utils, lock_applied, window_manager, AppliedProps, OverlayConfig, OverlayError, OverlayEvent, OverlayId,
+    OverlayManager, OverlayUI,
+};
+
+/// Rejects presets whose colors would not parse once applied
+fn validate_colors(preset: &OverlayConfig) -> Result<(), OverlayError> {
+    let colors = [Some(&preset.text.color), preset.fallback_color.as_ref(), preset.opaque_background.as_ref()];
+    for color in colors.into_iter().flatten() {
+        if color_utils::parse_argb(color).is_none() {
+            return Err(OverlayError::InvalidColor(color.clone()));
+        }
+    }
+    Ok(())
+}
+
+/// `config` restyled with the visual properties of `preset`; text, position, size and
+/// behavior (input passthrough, topmost, lock, ...) are kept
+fn restyle(config: &OverlayConfig, preset: &OverlayConfig) -> OverlayConfig {
+    let mut restyled = config.clone();
+    restyled.text.font_size = preset.text.font_size;
+    restyled.text.color = preset.text.color.clone();
+    restyled.fallback_color = preset.fallback_color.clone();
+    restyled.window_alpha = preset.window_alpha;
+    restyled.opaque_background = preset.opaque_background.clone();
+    restyled.pixel_snap = preset.pixel_snap;
+    restyled.validate();
+    restyled
+}
+
+/// Puts the visual properties `restyle` copies on the window
+fn apply_style(window: &OverlayUI, applied: &Mutex<AppliedProps>, config: &OverlayConfig) {
+    {
+        let mut applied = lock_applied(applied);
+        applied.set_font_size(window, config.text.font_size);
+        applied.set_color(window, config.text_color_argb());
+    }
+    window.set_pixel_snap(config.pixel_snap);
+    if config.transparent || config.window_alpha != window_manager::PER_PIXEL_ALPHA {
+        if let Ok(hwnd) = window_manager::get_native_handle(window.window()) {
+            let _ = window_manager::set_window_transparency(hwnd, config.window_alpha);
+        }
+    }
+}
+
+impl OverlayManager {
+    /// Registers a named style (e.g. "dark", "light") for `apply_preset_to_all`. Only the
+    /// visual properties of `preset` are used: font size, text and fallback colors, window
+    /// alpha, opaque background and pixel snapping. Fails with `InvalidColor` if any of its
+    /// colors doesn't parse. With a preset file set, the presets are saved to it.
+    pub fn set_preset(&self, name: &str, preset: OverlayConfig) -> Result<(), OverlayError> {
+        validate_colors(&preset)?;
+        let mut presets = self.presets.lock().map_err(|_| OverlayError::LockError)?;
+        presets.insert(name.to_string(), preset);
+        if let Some(path) = self.preset_file.lock().map_err(|_| OverlayError::LockError)?.as_deref() {
+            store_json(path, &*presets)?;
+        }
+        Ok(())
+    }
+
+    /// The registered presets by name
+    pub fn presets(&self) -> Result<HashMap<String, OverlayConfig>, OverlayError> {
+        self.presets.lock().map(|presets| presets.clone()).map_err(|_| OverlayError::LockError)
+    }
+
+    /// Keeps presets in a JSON file so they survive restarts: the presets saved in it are
+    /// loaded now (replacing registered ones with the same name), and every `set_preset`
+    /// writes the file again. Presets are only kept in memory until this is called.
+    pub fn set_preset_file(&self, path: impl Into<PathBuf>) -> Result<(), OverlayError> {
+        let path = path.into();
+        // A missing file (first run) has no presets yet
+        let saved: HashMap<String, OverlayConfig> = load_json(&path)?;
+        for preset in saved.values() {
+            validate_colors(preset)?;
+        }
+        self.presets.lock().map_err(|_| OverlayError::LockError)?.extend(saved);
+        *self.preset_file.lock().map_err(|_| OverlayError::LockError)? = Some(path);
+        Ok(())
+    }
+
+    /// Creates and shows an overlay with `text` at `(x, y)` in the style of the named
+    /// preset; everything the preset doesn't style comes from `set_defaults`, as with
+    /// `create_overlay_quick`.
+    pub fn create_overlay_with_preset(&self, name: &str, text: &str, x: i32, y: i32) -> Result<OverlayId, OverlayError> {
+        let preset = self
+            .presets
+            .lock()
+            .map_err(|_| OverlayError::LockError)?
+            .get(name)
+            .cloned()
+            .ok_or_else(|| OverlayError::PresetNotFound(name.to_string()))?;
+        let config = restyle(&self.quick_config(text, x, y)?, &preset);
+        let overlay_id = self.create_overlay(config)?;
+        self.show_overlay(&overlay_id)?;
+        Ok(overlay_id)
+    }
+
+    /// Restyles every overlay with the named preset, keeping each one's text and position,
+    /// and returns how many were restyled. Locked overlays are skipped. All windows are
+    /// updated in one UI-thread pass, so the scene switches theme without flicker.
+    pub fn apply_preset_to_all(&self, name: &str) -> Result<usize, OverlayError> {
+        let preset = self
+            .presets
+            .lock()
+            .map_err(|_| OverlayError::LockError)?
+            .get(name)
+            .cloned()
+            .ok_or_else(|| OverlayError::PresetNotFound(name.to_string()))?;
+
+        let mut overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let mut updates = Vec::new();
+        let mut events = Vec::new();
+        for (overlay_id, overlay) in overlays.iter_mut() {
+            if overlay.config.locked {
+                overlay_log!(info, overlay_id, "Not applying preset {} to locked overlay {}", name, overlay_id);
+                continue;
+            }
+            overlay.config = restyle(&overlay.config, &preset);
+            updates.push((
+                overlay_id.clone(),
+                overlay.window_weak.clone(),
+                Arc::clone(&overlay.applied),
+                overlay.config.clone(),
+            ));
+            events.push(OverlayEvent::ConfigUpdated {
+                id: overlay_id.clone(),
+                config: overlay.config.clone(),
+            });
+        }
+
+        crate::invoke_on_ui_thread(move || {
+            for (overlay_id, window_weak, applied, config) in updates {
+                let Some(window) = window_weak.upgrade() else {
+                    continue;
+                };
+                animation::cancel_color_animation(&overlay_id);
+                tasks::cancel(&overlay_id, TaskKind::Flash);
+                apply_style(&window, &applied, &config);
+            }
+        })?;
+        drop(overlays);
+
+        let restyled = events.len();
+        for event in events {
+            self.notify(event);
+        }
+        Ok(restyled)
+    }
+
+    /// Shows the overlay with the visual properties of `style` (the same ones a preset
+    /// sets) for `duration`, then puts its own style back, e.g. to flash a caption red for
+    /// two seconds. The overlay's config is never changed, so a second flash simply
+    /// replaces the first and still restores the original look.
+    pub fn flash_style(&self, overlay_id: &OverlayId, style: &OverlayConfig, duration: Duration) -> Result<(), OverlayError> {
+        let overlays = self.overlays.lock().map_err(|_| OverlayError::LockError)?;
+        let overlay = overlays
+            .get(overlay_id)
+            .ok_or_else(|| OverlayError::OverlayNotFound(overlay_id.clone()))?;
+        if overlay.config.locked {
+            return Err(OverlayError::Locked(overlay_id.clone()));
+        }
+
+        let flashed = restyle(&overlay.config, style);
+        let applied = Arc::clone(&overlay.applied);
+        let manager = self.clone();
+        let id = overlay_id.clone();
+
+        self.execute_ui_action(&overlay.window_weak, move |window| {
+            animation::cancel_color_animation(&id);
+            apply_style(&window, &applied, &flashed);
+
+            let weak = window.as_weak();
+            let restore_id = id.clone();
+            let timer = slint::Timer::default();
+            timer.start(slint::TimerMode::SingleShot, duration, move || {
+                let Some(window) = weak.upgrade() else {
+                    return;
+                };
+                // Whatever the config is by now, including updates made during the flash
+                let Some(config) = manager
+                    .overlays
+                    .lock()
+                    .ok()
+                    .and_then(|overlays| overlays.get(&restore_id).map(|overlay| overlay.config.clone()))
+                else {
+                    return;
+                };
+                apply_style(&window, &applied, &config);
+            });
+
+            // Replacing a pending flash drops its timer before it restores
+            tasks::register(&id, TaskKind::Flash, timer);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TextConfig;
+
+    #[test]
+    fn test_restyle_changes_colors_but_keeps_text() {
+        let overlay = OverlayConfig {
+            text: TextConfig {
+                content: "Hola".to_string(),
+                color: "#FFFFFFFF".to_string(),
+                position: (40, 900),
+                ..Default::default()
+            },
+            width: 640,
+            ignore_input: false,
+            ..Default::default()
+        };
+        let light = OverlayConfig {
+            text: TextConfig {
+                content: "ignored".to_string(),
+                color: "#FF101010".to_string(),
+                font_size: 30.0,
+                position: (0, 0),
+                ..Default::default()
+            },
+            window_alpha: 200,
+            ..Default::default()
+        };
+
+        let restyled = restyle(&overlay, &light);
+        assert_eq!(restyled.text.color, "#FF101010");
+        assert_eq!(restyled.text.font_size, 30.0);
+        assert_eq!(restyled.window_alpha, 200);
+        assert_eq!(restyled.text.content, "Hola");
+        assert_eq!(restyled.text.position, (40, 900));
+        assert_eq!(restyled.width, 640);
+        assert!(!restyled.ignore_input);
+    }
+
+    #[test]
+    fn test_presets_persist_and_validate_colors() {
+        let path = std::env::temp_dir().join(format!("subs_overlay_presets_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let manager = OverlayManager::new();
+        manager.set_preset_file(&path).unwrap();
+        let dark = OverlayConfig {
+            text: TextConfig {
+                color: "#FFEEEEEE".to_string(),
+                font_size: 28.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        manager.set_preset("dark", dark).unwrap();
+
+        let mut broken = OverlayConfig::default();
+        broken.text.color = "#nope".to_string();
+        assert!(matches!(manager.set_preset("broken", broken), Err(OverlayError::InvalidColor(_))));
+
+        let restarted = OverlayManager::new();
+        restarted.set_preset_file(&path).unwrap();
+        let presets = restarted.presets().unwrap();
+        assert_eq!(presets.len(), 1);
+        assert_eq!(presets["dark"].text.font_size, 28.0);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
+use crate::tasks::{self, TaskKind};
+use crate::{OverlayError, OverlayId, OverlayManager};
+
+/// When a visibility window shows and hides the overlay, counted from now.
+/// `show_in` is `None` when now is already inside the window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct VisibilityPlan {
+    show_in: Option<Duration>,
+    hide_in: Duration,
+}
+
+fn visibility_plan(
+    now: DateTime<Local>,
+    show_at: DateTime<Local>,
+    hide_at: DateTime<Local>,
+) -> Result<VisibilityPlan, OverlayError> {
+    if hide_at <= show_at {
+        return Err(OverlayError::InvalidSchedule(format!(
+            "hide time {} is not after show time {}",
+            hide_at, show_at
+        )));
+    }
+    let hide_in = (hide_at - now)
+        .to_std()
+        .map_err(|_| OverlayError::InvalidSchedule(format!("hide time {} has already passed", hide_at)))?;
+    // A negative span means the window has already started
+    let show_in = (show_at - now).to_std().ok().filter(|delay| !delay.is_zero());
+    Ok(VisibilityPlan { show_in, hide_in })
+}
+
+impl OverlayManager {
+    /// Shows the overlay at `show_at` and hides it at `hide_at` (local time), for banners
+    /// such as "from 20:00 to 20:30". If the window has already started the overlay shows
+    /// right away; until it starts the overlay is hidden. Scheduling again replaces the
+    /// previous schedule, and `cancel_visibility_schedule` drops it.
+    pub fn schedule_visibility(
+        &self,
+        overlay_id: &OverlayId,
+        show_at: DateTime<Local>,
+        hide_at: DateTime<Local>,
+    ) -> Result<(), OverlayError> {
+        self.ensure_exists(overlay_id)?;
+        let plan = visibility_plan(Local::now(), show_at, hide_at)?;
+
+        let manager = self.clone();
+        let id = overlay_id.clone();
+        crate::invoke_on_ui_thread(move || {
+            let show_timer = slint::Timer::default();
+            match plan.show_in {
+                Some(delay) => {
+                    if let Err(e) = manager.hide_overlay(&id) {
+                        overlay_log!(warn, &id, "Could not hide {} until its scheduled time: {}", id, e);
+                    }
+                    let show_manager = manager.clone();
+                    let show_id = id.clone();
+                    show_timer.start(slint::TimerMode::SingleShot, delay, move || {
+                        if let Err(e) = show_manager.show_overlay(&show_id) {
+                            overlay_log!(warn, &show_id, "Could not show scheduled overlay {}: {}", show_id, e);
+                        }
+                    });
+                }
+                None => {
+                    if let Err(e) = manager.show_overlay(&id) {
+                        overlay_log!(warn, &id, "Could not show scheduled overlay {}: {}", id, e);
+                    }
+                }
+            }
+
+            let hide_timer = slint::Timer::default();
+            let hide_id = id.clone();
+            hide_timer.start(slint::TimerMode::SingleShot, plan.hide_in, move || {
+                if let Err(e) = manager.hide_overlay(&hide_id) {
+                    overlay_log!(warn, &hide_id, "Could not hide scheduled overlay {}: {}", hide_id, e);
+                }
+            });
+            tasks::register(&id, TaskKind::Schedule, (show_timer, hide_timer));
+        })
+    }
+
+    /// Drops the visibility schedule, leaving the overlay shown or hidden as it is
+    pub fn cancel_visibility_schedule(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        let id = overlay_id.clone();
+        crate::invoke_on_ui_thread(move || tasks::cancel(&id, TaskKind::Schedule))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_visibility_plan() {
+        let at = |h, m| Local.with_ymd_and_hms(2024, 5, 1, h, m, 0).unwrap();
+
+        // Before the window: wait for both ends
+        let plan = visibility_plan(at(19, 50), at(20, 0), at(20, 30)).unwrap();
+        assert_eq!(plan.show_in, Some(Duration::from_secs(600)));
+        assert_eq!(plan.hide_in, Duration::from_secs(2400));
+
+        // Already inside the window: show now, hide at the end
+        let plan = visibility_plan(at(20, 10), at(20, 0), at(20, 30)).unwrap();
+        assert_eq!(plan.show_in, None);
+        assert_eq!(plan.hide_in, Duration::from_secs(1200));
+
+        assert!(matches!(
+            visibility_plan(at(20, 40), at(20, 0), at(20, 30)),
+            Err(OverlayError::InvalidSchedule(_))
+        ));
+        assert!(matches!(
+            visibility_plan(at(19, 0), at(20, 30), at(20, 0)),
+            Err(OverlayError::InvalidSchedule(_))
+        ));
+    }
+}
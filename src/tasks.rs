@@ -0,0 +1,134 @@
+//! Registry of the background work overlays own: timers, bindings and capture threads.
+//!
+//! Every task is a guard whose drop stops the work (a `slint::Timer`, a binding that restores
+//! the overlay, a sender its thread waits on). Keeping them all here, keyed by overlay id,
+//! lets removal and shutdown cancel everything an overlay started in one place, so no
+//! callback fires for an overlay that is gone. The registry lives on the UI thread.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::OverlayId;
+
+/// What a task does; an overlay has at most one task of each kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum TaskKind {
+    Clipboard,
+    Clock,
+    Karaoke,
+    ColorAnimation,
+    Flash,
+    FadeOut,
+    AlphaHitTest,
+    IdleOpacity,
+    Playlist,
+    Schedule,
+    #[cfg(feature = "audio")]
+    Audio,
+}
+
+type OverlayTasks = HashMap<TaskKind, Box<dyn Any>>;
+
+thread_local! {
+    static TASKS: RefCell<HashMap<OverlayId, OverlayTasks>> = RefCell::new(HashMap::new());
+}
+
+/// Registers `task` for an overlay, stopping the task of the same kind it replaces.
+/// Must run on the UI thread.
+pub(crate) fn register<T: Any>(overlay_id: &OverlayId, kind: TaskKind, task: T) {
+    let replaced = TASKS.with(|tasks| {
+        tasks
+            .borrow_mut()
+            .entry(overlay_id.clone())
+            .or_default()
+            .insert(kind, Box::new(task))
+    });
+    // Dropped outside the borrow, since stopping a task may run arbitrary code
+    drop(replaced);
+}
+
+/// Runs `action` on an overlay's task of `kind`, if it has one of type `T`
+pub(crate) fn with_task<T: Any, R>(overlay_id: &OverlayId, kind: TaskKind, action: impl FnOnce(&T) -> R) -> Option<R> {
+    TASKS.with(|tasks| {
+        tasks
+            .borrow()
+            .get(overlay_id)
+            .and_then(|overlay_tasks| overlay_tasks.get(&kind))
+            .and_then(|task| task.downcast_ref::<T>())
+            .map(action)
+    })
+}
+
+/// Stops an overlay's task of `kind`, if any. Must run on the UI thread.
+pub(crate) fn cancel(overlay_id: &OverlayId, kind: TaskKind) {
+    let cancelled = TASKS.with(|tasks| {
+        let mut tasks = tasks.borrow_mut();
+        let overlay_tasks = tasks.get_mut(overlay_id)?;
+        let task = overlay_tasks.remove(&kind);
+        if overlay_tasks.is_empty() {
+            tasks.remove(overlay_id);
+        }
+        task
+    });
+    drop(cancelled);
+}
+
+/// Stops every task of an overlay. Must run on the UI thread.
+pub(crate) fn cancel_overlay(overlay_id: &OverlayId) {
+    let cancelled = TASKS.with(|tasks| tasks.borrow_mut().remove(overlay_id));
+    drop(cancelled);
+}
+
+/// Stops every task of every overlay. Must run on the UI thread.
+pub(crate) fn cancel_all() {
+    let cancelled = TASKS.with(|tasks| std::mem::take(&mut *tasks.borrow_mut()));
+    drop(cancelled);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Stands in for a timer: records whether it was stopped
+    struct Guard(Rc<Cell<bool>>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    fn guard() -> (Guard, Rc<Cell<bool>>) {
+        let stopped = Rc::new(Cell::new(false));
+        (Guard(Rc::clone(&stopped)), stopped)
+    }
+
+    #[test]
+    fn test_tasks_are_cancelled_with_their_overlay() {
+        let (captions, other) = ("captions".to_string(), "other".to_string());
+        let (first_clock, first_stopped) = guard();
+        let (clock, clock_stopped) = guard();
+        let (karaoke, karaoke_stopped) = guard();
+        let (other_clock, other_stopped) = guard();
+
+        register(&captions, TaskKind::Clock, first_clock);
+        // A task of the same kind replaces, and stops, the previous one
+        register(&captions, TaskKind::Clock, clock);
+        assert!(first_stopped.get());
+        register(&captions, TaskKind::Karaoke, karaoke);
+        register(&other, TaskKind::Clock, other_clock);
+        assert_eq!(with_task(&captions, TaskKind::Karaoke, |_: &Guard| ()), Some(()));
+
+        // Removing the overlay early stops all of its work, and only its work
+        cancel_overlay(&captions);
+        assert!(clock_stopped.get() && karaoke_stopped.get());
+        assert!(with_task(&captions, TaskKind::Clock, |_: &Guard| ()).is_none());
+        assert!(!other_stopped.get());
+
+        cancel_all();
+        assert!(other_stopped.get());
+    }
+}
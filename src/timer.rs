@@ -0,0 +1,177 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::tasks::{self, TaskKind};
+use crate::{OverlayConfig, OverlayError, OverlayId, OverlayManager};
+
+/// How often the clock is re-evaluated; the text only changes once per second
+const TICK: Duration = Duration::from_millis(200);
+
+type OnComplete = Box<dyn FnOnce() + Send>;
+
+struct ClockState {
+    duration: Duration,
+    count_down: bool,
+    /// Time accumulated before the current run
+    elapsed: Duration,
+    running_since: Option<Instant>,
+    on_complete: Option<OnComplete>,
+    last_text: String,
+}
+
+impl ClockState {
+    fn elapsed(&self) -> Duration {
+        let running = self.running_since.map(|since| since.elapsed()).unwrap_or_default();
+        (self.elapsed + running).min(self.duration)
+    }
+
+    fn text(&self) -> String {
+        let elapsed = self.elapsed();
+        if self.count_down {
+            // Round up so the clock reads 00:00 only once time is actually up
+            let remaining = self.duration - elapsed;
+            let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+            format_clock(Duration::from_secs(secs))
+        } else {
+            format_clock(elapsed)
+        }
+    }
+
+    fn pause(&mut self) {
+        if let Some(since) = self.running_since.take() {
+            self.elapsed += since.elapsed();
+        }
+    }
+}
+
+type Clock = (slint::Timer, Rc<RefCell<ClockState>>);
+
+/// Formats a duration as `MM:SS`, or `HH:MM:SS` from one hour up
+pub fn format_clock(duration: Duration) -> String {
+    let total = duration.as_secs();
+    let (hours, minutes, seconds) = (total / 3600, (total % 3600) / 60, total % 60);
+    if hours > 0 {
+        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+fn with_clock<F>(overlay_id: &OverlayId, action: F) -> Result<(), OverlayError>
+where
+    F: FnOnce(&mut ClockState) + Send + 'static,
+{
+    let id = overlay_id.clone();
+    crate::invoke_on_ui_thread(move || {
+        let found = tasks::with_task(&id, TaskKind::Clock, |(_, state): &Clock| action(&mut state.borrow_mut()));
+        if found.is_none() {
+            overlay_log!(warn, &id, "Overlay {} has no timer", id);
+        }
+    })
+}
+
+impl OverlayManager {
+    /// Creates and shows an overlay that displays a countdown (`count_down`) or a stopwatch.
+    ///
+    /// The countdown runs from `duration` to zero; the stopwatch stops once it reaches `duration`.
+    /// Either way `on_complete` fires once at the end. Must be called from the UI thread.
+    pub fn create_timer_overlay(
+        &self,
+        config: OverlayConfig,
+        duration: Duration,
+        count_down: bool,
+        on_complete: Option<Box<dyn FnOnce() + Send>>,
+    ) -> Result<OverlayId, OverlayError> {
+        let state = ClockState {
+            duration,
+            count_down,
+            elapsed: Duration::ZERO,
+            running_since: Some(Instant::now()),
+            on_complete,
+            last_text: String::new(),
+        };
+
+        let mut config = config;
+        config.text.content = state.text();
+        let overlay_id = self.create_overlay(config)?;
+        self.show_overlay(&overlay_id)?;
+
+        let state = Rc::new(RefCell::new(state));
+        let timer = slint::Timer::default();
+        let manager = self.clone();
+        let id = overlay_id.clone();
+        let tick_state = Rc::clone(&state);
+        timer.start(slint::TimerMode::Repeated, TICK, move || {
+            let mut state = tick_state.borrow_mut();
+
+            let text = state.text();
+            if text != state.last_text {
+                if let Err(e) = manager.update_text(&id, &text) {
+                    overlay_log!(warn, &id, "Could not update timer overlay {}: {}", id, e);
+                }
+                state.last_text = text;
+            }
+
+            if state.running_since.is_some() && state.elapsed() >= state.duration {
+                state.pause();
+                if let Some(on_complete) = state.on_complete.take() {
+                    on_complete();
+                }
+            }
+        });
+
+        tasks::register(&overlay_id, TaskKind::Clock, (timer, state));
+
+        Ok(overlay_id)
+    }
+
+    /// Pauses a timer overlay, keeping its current reading
+    pub fn pause_timer(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        with_clock(overlay_id, |state| state.pause())
+    }
+
+    /// Resumes a paused timer overlay
+    pub fn resume_timer(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        with_clock(overlay_id, |state| {
+            if state.running_since.is_none() && state.elapsed < state.duration {
+                state.running_since = Some(Instant::now());
+            }
+        })
+    }
+
+    /// Restarts a timer overlay from the beginning. `on_complete` does not fire again.
+    pub fn reset_timer(&self, overlay_id: &OverlayId) -> Result<(), OverlayError> {
+        with_clock(overlay_id, |state| {
+            state.elapsed = Duration::ZERO;
+            state.running_since = Some(Instant::now());
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_clock() {
+        assert_eq!(format_clock(Duration::from_secs(0)), "00:00");
+        assert_eq!(format_clock(Duration::from_secs(65)), "01:05");
+        assert_eq!(format_clock(Duration::from_secs(3599)), "59:59");
+        assert_eq!(format_clock(Duration::from_secs(3600)), "01:00:00");
+        assert_eq!(format_clock(Duration::from_secs(36_000 + 61)), "10:01:01");
+    }
+
+    #[test]
+    fn test_countdown_rounds_up() {
+        let state = ClockState {
+            duration: Duration::from_secs(10),
+            count_down: true,
+            elapsed: Duration::from_millis(500),
+            running_since: None,
+            on_complete: None,
+            last_text: String::new(),
+        };
+        assert_eq!(state.text(), "00:10");
+    }
+}
@@ -1,11 +1,125 @@
 use slint::Window;
-use windows::Win32::Foundation::{COLORREF, HWND};
+use std::sync::Once;
+use windows::Win32::Foundation::{BOOL, COLORREF, HWND, LPARAM, POINT, RECT};
+use windows::core::{s, w, HSTRING, PCWSTR};
+use windows::Win32::Graphics::Dwm::{
+    DwmExtendFrameIntoClientArea, DwmIsCompositionEnabled, DwmSetWindowAttribute, DWMSBT_TRANSIENTWINDOW, DWMWA_SYSTEMBACKDROP_TYPE,
+};
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors, GetMonitorInfoW, MonitorFromWindow, HDC, HMONITOR, MONITORINFO, MONITOR_DEFAULTTONEAREST,
+};
+use windows::Win32::System::LibraryLoader::{GetModuleHandleW, GetProcAddress};
+use windows::Win32::System::SystemInformation::GetTickCount;
+use windows::Win32::UI::Controls::MARGINS;
+use windows::Win32::UI::HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, SetFocus, LASTINPUTINFO};
 use windows::Win32::UI::WindowsAndMessaging::{
-    GetWindowLongW, SetLayeredWindowAttributes, SetWindowLongW, SetWindowPos, ShowWindow,
-    GWL_EXSTYLE, HWND_TOPMOST, LWA_ALPHA, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SW_HIDE, SW_SHOW,
-    WS_EX_LAYERED, WS_EX_TRANSPARENT,
+    FindWindowW, FlashWindowEx, GetCursorPos, GetForegroundWindow, GetWindow, GetWindowLongW, GetWindowRect, IsWindow, SetForegroundWindow,
+    SetLayeredWindowAttributes, SetWindowDisplayAffinity, SetWindowLongW, SetWindowPos, ShowWindow, FLASHWINFO, FLASHW_ALL,
+    FLASHW_TIMERNOFG, GWL_EXSTYLE, GW_HWNDPREV, HWND_NOTOPMOST, HWND_TOP, HWND_TOPMOST, LWA_ALPHA, MONITORINFOF_PRIMARY, SWP_NOACTIVATE,
+    SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SW_HIDE, SW_SHOW, WDA_EXCLUDEFROMCAPTURE, WDA_NONE, WS_EX_LAYERED, WS_EX_TOPMOST,
+    WS_EX_TRANSPARENT,
 };
 
+/// Layer alpha used for transparent overlays.
+///
+/// A layered window needs `SetLayeredWindowAttributes` to be drawn at all, but any value
+/// below 255 dims the whole window uniformly, text included. At 255 the layer is neutral and
+/// the Slint surface's own per-pixel alpha decides what shows: the transparent background
+/// stays fully see-through while opaque text renders at full opacity. Uniform dimming is a
+/// separate, explicit choice made through `set_window_transparency`.
+pub const PER_PIXEL_ALPHA: u8 = 255;
+
+/// A rectangle in virtual-screen pixel coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ScreenRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl From<RECT> for ScreenRect {
+    fn from(rect: RECT) -> Self {
+        Self {
+            x: rect.left,
+            y: rect.top,
+            width: rect.right - rect.left,
+            height: rect.bottom - rect.top,
+        }
+    }
+}
+
+impl ScreenRect {
+    fn center(&self) -> (i64, i64) {
+        (
+            self.x as i64 + self.width as i64 / 2,
+            self.y as i64 + self.height as i64 / 2,
+        )
+    }
+
+    /// Squared distance from a point to the closest point of this rectangle
+    fn distance_sq(&self, (px, py): (i64, i64)) -> i64 {
+        let dx = (self.x as i64 - px).max(0).max(px - (self.x as i64 + self.width as i64));
+        let dy = (self.y as i64 - py).max(0).max(py - (self.y as i64 + self.height as i64));
+        dx * dx + dy * dy
+    }
+
+    /// Shrinks the rectangle by `percent` of its width and height on every side (TV safe area).
+    /// The percentage is clamped to 0..50.
+    pub fn inset(&self, percent: f32) -> ScreenRect {
+        let fraction = percent.clamp(0.0, 50.0) as f64 / 100.0;
+        let dx = px_round(self.width as f64 * fraction);
+        let dy = px_round(self.height as f64 * fraction);
+        ScreenRect {
+            x: self.x + dx,
+            y: self.y + dy,
+            width: self.width - 2 * dx,
+            height: self.height - 2 * dy,
+        }
+    }
+}
+
+/// Converts a float position or size to whole pixels.
+///
+/// Rounds half to even (so 0.5 -> 0, 1.5 -> 2, -2.5 -> -2), which avoids drifting in one
+/// direction when the same values are converted repeatedly. Out-of-range values saturate
+/// at the `i32` bounds and NaN becomes 0. Use this wherever a float becomes a pixel.
+pub fn px_round(value: f64) -> i32 {
+    value.round_ties_even() as i32
+}
+
+/// Description of a connected display
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MonitorInfo {
+    pub index: usize,
+    pub bounds: ScreenRect,
+    pub work_area: ScreenRect,
+    pub primary: bool,
+    /// DPI scale relative to 96 DPI (1.0 = 100%)
+    pub scale_factor: f32,
+}
+
+/// How long a window flashes when requesting attention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum AttentionMode {
+    /// Flash the caption and taskbar button this many times
+    Count(u32),
+    /// Keep flashing until the window comes to the foreground
+    UntilFocused,
+}
+
+/// Blur effect applied behind a window by `enable_acrylic`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backdrop {
+    /// Windows 11 system backdrop; the tint has to be painted by the window itself
+    SystemAcrylic,
+    /// Windows 10 accent blur, tinted by the compositor
+    AccentAcrylic,
+    /// No blur available
+    Unsupported,
+}
+
 /// Applies window properties like transparency and input ignoring
 pub fn apply_window_properties(
     hwnd: HWND,
@@ -31,7 +145,7 @@ pub fn apply_window_properties(
         // Set transparency
         if transparent {
             // Set alpha transparency
-            SetLayeredWindowAttributes(hwnd, COLORREF(0), 255, LWA_ALPHA)?;
+            SetLayeredWindowAttributes(hwnd, COLORREF(0), PER_PIXEL_ALPHA, LWA_ALPHA)?;
         }
 
         // Make always on top
@@ -43,6 +157,47 @@ pub fn apply_window_properties(
     Ok(())
 }
 
+/// Makes mouse input pass through the window, or restores normal hit-testing
+pub fn set_input_passthrough(hwnd: HWND, ignore_input: bool) -> Result<(), Box<dyn std::error::Error>> {
+    unsafe {
+        let mut ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE);
+        if ignore_input {
+            ex_style |= (WS_EX_LAYERED.0 | WS_EX_TRANSPARENT.0) as i32;
+        } else {
+            ex_style &= !(WS_EX_TRANSPARENT.0 as i32);
+        }
+        SetWindowLongW(hwnd, GWL_EXSTYLE, ex_style);
+    }
+
+    Ok(())
+}
+
+/// Current mouse cursor position in screen coordinates
+pub fn cursor_position() -> Result<(i32, i32), Box<dyn std::error::Error>> {
+    let mut point = POINT::default();
+    unsafe {
+        GetCursorPos(&mut point)?;
+    }
+
+    Ok((point.x, point.y))
+}
+
+/// Time since the last keyboard or mouse input anywhere in the session
+pub fn idle_time() -> Result<std::time::Duration, Box<dyn std::error::Error>> {
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    unsafe {
+        if !GetLastInputInfo(&mut info).as_bool() {
+            return Err("GetLastInputInfo failed".into());
+        }
+        // Both are tick counts that wrap after ~49 days
+        let idle_ms = GetTickCount().wrapping_sub(info.dwTime);
+        Ok(std::time::Duration::from_millis(idle_ms as u64))
+    }
+}
+
 /// Shows or hides a window
 pub fn set_window_visibility(hwnd: HWND, visible: bool) -> Result<(), Box<dyn std::error::Error>> {
     unsafe {
@@ -65,6 +220,28 @@ pub fn set_window_position(hwnd: HWND, x: i32, y: i32) -> Result<(), Box<dyn std
     Ok(())
 }
 
+/// Excludes the window from screen capture while keeping it visible on the local display.
+///
+/// Requires Windows 10 version 2004 or later; older versions reject the affinity.
+pub fn set_capture_excluded(hwnd: HWND, excluded: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let affinity = if excluded { WDA_EXCLUDEFROMCAPTURE } else { WDA_NONE };
+    unsafe {
+        SetWindowDisplayAffinity(hwnd, affinity)?;
+    }
+
+    Ok(())
+}
+
+/// Gets the window's current rectangle in screen coordinates
+pub fn get_window_rect(hwnd: HWND) -> Result<ScreenRect, Box<dyn std::error::Error>> {
+    let mut rect = RECT::default();
+    unsafe {
+        GetWindowRect(hwnd, &mut rect)?;
+    }
+
+    Ok(rect.into())
+}
+
 /// Gets the native window handle from a Slint window
 pub fn get_native_handle(window: &Window) -> Result<HWND, Box<dyn std::error::Error>> {
     use raw_window_handle::{HasWindowHandle, RawWindowHandle};
@@ -76,7 +253,59 @@ pub fn get_native_handle(window: &Window) -> Result<HWND, Box<dyn std::error::Er
             // Convert NonZeroIsize to HWND (isize)
             Ok(HWND(handle.hwnd.get()))
         }
-        _ => Err("Not a Windows window".into()),
+        other => {
+            static WARNED: Once = Once::new();
+            WARNED.call_once(|| {
+                log::warn!(
+                    "Window handle is {:?}, not Win32: click-through, always-on-top and transparency \
+                     are not applied on this platform",
+                    other
+                );
+            });
+            Err("Not a Windows window".into())
+        }
+    }
+}
+
+/// What the native window layer can do on the current platform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct WindowManagerCapabilities {
+    native: bool,
+}
+
+impl WindowManagerCapabilities {
+    /// Mouse input passes through `ignore_input` overlays
+    pub fn supports_click_through(&self) -> bool {
+        self.native
+    }
+
+    /// `always_on_top` keeps overlays above other windows
+    pub fn supports_always_on_top(&self) -> bool {
+        self.native
+    }
+
+    /// `window_alpha` and layered transparency are applied
+    pub fn supports_window_alpha(&self) -> bool {
+        self.native
+    }
+
+    /// Monitors can be enumerated for clamping, snapping and safe areas
+    pub fn supports_monitor_info(&self) -> bool {
+        self.native
+    }
+}
+
+/// Whether the desktop compositor is running, which per-pixel transparency relies on.
+/// It can be off in remote desktop sessions and some virtual machines.
+pub fn composition_enabled() -> bool {
+    capabilities().supports_window_alpha()
+        && unsafe { DwmIsCompositionEnabled() }.map(|enabled| enabled.as_bool()).unwrap_or(false)
+}
+
+/// Reports which native window features work here; only the Win32 backend exists so far
+pub fn capabilities() -> WindowManagerCapabilities {
+    WindowManagerCapabilities {
+        native: cfg!(target_os = "windows"),
     }
 }
 
@@ -98,7 +327,7 @@ pub fn create_transparent_click_through_window(
         SetWindowLongW(hwnd, GWL_EXSTYLE, ex_style);
 
         // Set window transparency
-        SetLayeredWindowAttributes(hwnd, COLORREF(0), 255, LWA_ALPHA)?;
+        SetLayeredWindowAttributes(hwnd, COLORREF(0), PER_PIXEL_ALPHA, LWA_ALPHA)?;
     }
 
     Ok(())
@@ -113,7 +342,7 @@ pub fn set_always_on_top(
         let hwnd_insert_after = if always_on_top {
             HWND_TOPMOST
         } else {
-            HWND_TOPMOST // Using HWND_TOPMOST for simplicity; should be HWND_NOTOPMOST
+            HWND_NOTOPMOST
         };
 
         SetWindowPos(hwnd, hwnd_insert_after, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE)?;
@@ -122,6 +351,69 @@ pub fn set_always_on_top(
     Ok(())
 }
 
+/// Window an overlay is stacked against by `set_relative_zorder`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZOrderTarget {
+    /// Raw window handle (`HWND`)
+    Handle(isize),
+    /// Exact window title
+    Title(String),
+}
+
+/// Resolves a z-order target to a live window handle
+pub fn find_window(target: &ZOrderTarget) -> Option<HWND> {
+    let hwnd = match target {
+        ZOrderTarget::Handle(handle) => HWND(*handle),
+        ZOrderTarget::Title(title) => unsafe { FindWindowW(PCWSTR::null(), &HSTRING::from(title.as_str())) },
+    };
+
+    if hwnd.0 != 0 && unsafe { IsWindow(hwnd) }.as_bool() {
+        Some(hwnd)
+    } else {
+        None
+    }
+}
+
+/// Places the window directly above or below `target` in the z-order
+pub fn set_relative_zorder(hwnd: HWND, target: HWND, above: bool) -> Result<(), Box<dyn std::error::Error>> {
+    unsafe {
+        // SetWindowPos inserts *after* (below) the given window, so going above the
+        // target means inserting after whatever currently sits directly above it
+        let insert_after = if above {
+            let previous = GetWindow(target, GW_HWNDPREV);
+            if previous == hwnd {
+                // Already directly above the target
+                return Ok(());
+            }
+            if previous.0 == 0 { HWND_TOP } else { previous }
+        } else {
+            target
+        };
+
+        SetWindowPos(hwnd, insert_after, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE)?;
+    }
+
+    Ok(())
+}
+
+/// Stacks topmost windows in the given order, bottom to top, so the last one ends up above
+/// the others. Windows are raised without being activated.
+pub fn stack_topmost(hwnds: &[HWND]) -> Result<(), Box<dyn std::error::Error>> {
+    for &hwnd in hwnds {
+        unsafe {
+            SetWindowPos(hwnd, HWND_TOPMOST, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the window currently has the topmost extended style
+pub fn is_topmost(hwnd: HWND) -> bool {
+    let ex_style = unsafe { GetWindowLongW(hwnd, GWL_EXSTYLE) };
+    ex_style as u32 & WS_EX_TOPMOST.0 != 0
+}
+
 /// Sets window transparency level (0-255, where 255 is fully opaque)
 pub fn set_window_transparency(hwnd: HWND, alpha: u8) -> Result<(), Box<dyn std::error::Error>> {
     unsafe {
@@ -138,3 +430,344 @@ pub fn set_window_transparency(hwnd: HWND, alpha: u8) -> Result<(), Box<dyn std:
 
     Ok(())
 }
+
+/// Lists the connected monitors in system enumeration order.
+///
+/// Returns an empty list if the monitors can't be enumerated.
+pub fn enumerate_monitors() -> Vec<MonitorInfo> {
+    unsafe extern "system" fn collect(hmonitor: HMONITOR, _hdc: HDC, _rect: *mut RECT, data: LPARAM) -> BOOL {
+        let handles = &mut *(data.0 as *mut Vec<HMONITOR>);
+        handles.push(hmonitor);
+        BOOL(1)
+    }
+
+    let mut handles: Vec<HMONITOR> = Vec::new();
+    let ok = unsafe {
+        EnumDisplayMonitors(
+            HDC::default(),
+            None,
+            Some(collect),
+            LPARAM(&mut handles as *mut Vec<HMONITOR> as isize),
+        )
+    };
+    if !ok.as_bool() {
+        log::warn!("Could not enumerate monitors");
+        return Vec::new();
+    }
+
+    handles
+        .into_iter()
+        .filter_map(|hmonitor| {
+            let mut info = MONITORINFO {
+                cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+                ..Default::default()
+            };
+            if !unsafe { GetMonitorInfoW(hmonitor, &mut info) }.as_bool() {
+                return None;
+            }
+
+            let (mut dpi_x, mut dpi_y) = (96u32, 96u32);
+            let scale_factor = match unsafe { GetDpiForMonitor(hmonitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) } {
+                Ok(()) => dpi_x as f32 / 96.0,
+                Err(_) => 1.0,
+            };
+
+            Some((info, scale_factor))
+        })
+        .enumerate()
+        .map(|(index, (info, scale_factor))| MonitorInfo {
+            index,
+            bounds: info.rcMonitor.into(),
+            work_area: info.rcWork.into(),
+            primary: info.dwFlags & MONITORINFOF_PRIMARY != 0,
+            scale_factor,
+        })
+        .collect()
+}
+
+/// DPI scale factor of the monitor the window is on (1.0 at 96 DPI)
+pub fn window_scale_factor(hwnd: HWND) -> Result<f32, Box<dyn std::error::Error>> {
+    let (mut dpi_x, mut dpi_y) = (96u32, 96u32);
+    unsafe {
+        let hmonitor = MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST);
+        GetDpiForMonitor(hmonitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y)?;
+    }
+
+    Ok(dpi_x as f32 / 96.0)
+}
+
+/// Brings the window to the foreground with keyboard focus. Returns the window that was in
+/// the foreground before, so `blur_window` can hand focus back to it.
+pub fn focus_window(hwnd: HWND) -> Result<HWND, Box<dyn std::error::Error>> {
+    unsafe {
+        let previous = GetForegroundWindow();
+        // Windows refuses this unless the calling process may set the foreground window
+        if !SetForegroundWindow(hwnd).as_bool() {
+            return Err("SetForegroundWindow was refused".into());
+        }
+        SetFocus(hwnd);
+        Ok(previous)
+    }
+}
+
+/// Gives the foreground back to `previous` if `hwnd` still has it, or just drops the
+/// keyboard focus when there is no previous window to return to
+pub fn blur_window(hwnd: HWND, previous: Option<HWND>) -> Result<(), Box<dyn std::error::Error>> {
+    unsafe {
+        if GetForegroundWindow() != hwnd {
+            return Ok(());
+        }
+        match previous.filter(|previous| previous.0 != 0 && IsWindow(*previous).as_bool()) {
+            Some(previous) => {
+                let _ = SetForegroundWindow(previous);
+            }
+            None => {
+                SetFocus(HWND(0));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Flashes the window caption and taskbar button to request the user's attention
+pub fn flash_window(hwnd: HWND, mode: AttentionMode) -> Result<(), Box<dyn std::error::Error>> {
+    let (flags, count) = match mode {
+        AttentionMode::Count(count) => (FLASHW_ALL, count),
+        AttentionMode::UntilFocused => (FLASHW_ALL | FLASHW_TIMERNOFG, 0),
+    };
+
+    let info = FLASHWINFO {
+        cbSize: std::mem::size_of::<FLASHWINFO>() as u32,
+        hwnd,
+        dwFlags: flags,
+        uCount: count,
+        dwTimeout: 0,
+    };
+
+    unsafe {
+        // The return value is the previous flash state, not an error indicator
+        let _ = FlashWindowEx(&info);
+    }
+
+    Ok(())
+}
+
+#[repr(C)]
+struct AccentPolicy {
+    accent_state: u32,
+    accent_flags: u32,
+    gradient_color: u32,
+    animation_id: u32,
+}
+
+#[repr(C)]
+struct WindowCompositionAttribData {
+    attrib: u32,
+    data: *mut std::ffi::c_void,
+    size: usize,
+}
+
+const WCA_ACCENT_POLICY: u32 = 19;
+const ACCENT_ENABLE_ACRYLICBLURBEHIND: u32 = 4;
+
+/// Applies the undocumented Windows 10 accent blur through `SetWindowCompositionAttribute`
+fn set_accent_acrylic(hwnd: HWND, tint_argb: u32) -> bool {
+    type SetWindowCompositionAttribute = unsafe extern "system" fn(HWND, *mut WindowCompositionAttribData) -> BOOL;
+
+    unsafe {
+        let Ok(user32) = GetModuleHandleW(w!("user32.dll")) else {
+            return false;
+        };
+        let Some(proc) = GetProcAddress(user32, s!("SetWindowCompositionAttribute")) else {
+            return false;
+        };
+        let set_attribute: SetWindowCompositionAttribute = std::mem::transmute(proc);
+
+        // The accent expects the tint as AABBGGRR
+        let (a, r, g, b) = (tint_argb >> 24, (tint_argb >> 16) & 0xFF, (tint_argb >> 8) & 0xFF, tint_argb & 0xFF);
+        let mut policy = AccentPolicy {
+            accent_state: ACCENT_ENABLE_ACRYLICBLURBEHIND,
+            accent_flags: 2,
+            gradient_color: (a << 24) | (b << 16) | (g << 8) | r,
+            animation_id: 0,
+        };
+        let mut data = WindowCompositionAttribData {
+            attrib: WCA_ACCENT_POLICY,
+            data: &mut policy as *mut AccentPolicy as *mut std::ffi::c_void,
+            size: std::mem::size_of::<AccentPolicy>(),
+        };
+
+        set_attribute(hwnd, &mut data).as_bool()
+    }
+}
+
+/// Blurs the content behind the window, trying the Windows 11 system backdrop first
+/// and then the Windows 10 accent blur. `tint_argb` is only applied by the latter.
+pub fn enable_acrylic(hwnd: HWND, tint_argb: u32) -> Backdrop {
+    unsafe {
+        let margins = MARGINS {
+            cxLeftWidth: -1,
+            cxRightWidth: -1,
+            cyTopHeight: -1,
+            cyBottomHeight: -1,
+        };
+        let backdrop = DWMSBT_TRANSIENTWINDOW;
+        let system_backdrop = DwmExtendFrameIntoClientArea(hwnd, &margins).is_ok()
+            && DwmSetWindowAttribute(
+                hwnd,
+                DWMWA_SYSTEMBACKDROP_TYPE,
+                &backdrop as *const _ as *const std::ffi::c_void,
+                std::mem::size_of_val(&backdrop) as u32,
+            )
+            .is_ok();
+        if system_backdrop {
+            return Backdrop::SystemAcrylic;
+        }
+    }
+
+    if set_accent_acrylic(hwnd, tint_argb) {
+        Backdrop::AccentAcrylic
+    } else {
+        Backdrop::Unsupported
+    }
+}
+
+/// Moves `rect` so it lies within the work area of the monitor nearest to its center.
+///
+/// Rectangles larger than the work area are aligned to its top-left corner. The size is
+/// never changed. With no monitors the rectangle is returned unchanged.
+pub fn clamp_rect_to_monitors(rect: ScreenRect, monitors: &[MonitorInfo]) -> ScreenRect {
+    let center = rect.center();
+    let Some(area) = monitors
+        .iter()
+        .map(|monitor| monitor.work_area)
+        .min_by_key(|area| area.distance_sq(center))
+    else {
+        return rect;
+    };
+
+    let x = rect.x.min(area.x + area.width - rect.width).max(area.x);
+    let y = rect.y.min(area.y + area.height - rect.height).max(area.y);
+    ScreenRect { x, y, ..rect }
+}
+
+/// Snaps `rect` to nearby edges of `targets` (monitor work areas and other overlays)
+/// that lie within `threshold` pixels, independently on each axis.
+pub fn snap_rect(rect: ScreenRect, targets: &[ScreenRect], threshold: i32) -> ScreenRect {
+    // Smallest offset that aligns either edge of `[start, start + len)` with either edge of a target
+    let snap_axis = |start: i32, len: i32, edges: &mut dyn Iterator<Item = (i32, i32)>| {
+        edges
+            .flat_map(|(target_start, target_end)| {
+                [target_start - start, target_end - start, target_start - (start + len), target_end - (start + len)]
+            })
+            .filter(|offset| offset.abs() <= threshold)
+            .min_by_key(|offset| offset.abs())
+            .unwrap_or(0)
+    };
+
+    let dx = snap_axis(rect.x, rect.width, &mut targets.iter().map(|t| (t.x, t.x + t.width)));
+    let dy = snap_axis(rect.y, rect.height, &mut targets.iter().map(|t| (t.y, t.y + t.height)));
+    ScreenRect { x: rect.x + dx, y: rect.y + dy, ..rect }
+}
+
+/// Rounds `value` to the nearest multiple of `grid`; `0` leaves it unchanged
+pub fn snap_to_grid(value: i32, grid: u32) -> i32 {
+    if grid == 0 {
+        return value;
+    }
+    let grid = grid as f64;
+    ((value as f64 / grid).round() * grid).clamp(i32::MIN as f64, i32::MAX as f64) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(index: usize, x: i32, y: i32, width: i32, height: i32) -> MonitorInfo {
+        let bounds = ScreenRect { x, y, width, height };
+        MonitorInfo {
+            index,
+            bounds,
+            work_area: bounds,
+            primary: index == 0,
+            scale_factor: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_clamp_rect_to_monitors() {
+        let monitors = [monitor(0, 0, 0, 1920, 1080), monitor(1, 1920, 0, 1280, 1024)];
+        let rect = |x, y| ScreenRect { x, y, width: 400, height: 100 };
+
+        // Already visible
+        assert_eq!(clamp_rect_to_monitors(rect(100, 100), &monitors), rect(100, 100));
+        // Off the bottom-right of the second monitor
+        assert_eq!(clamp_rect_to_monitors(rect(5000, 2000), &monitors), rect(2800, 924));
+        // Off the top-left of the primary monitor
+        assert_eq!(clamp_rect_to_monitors(rect(-500, -50), &monitors), rect(0, 0));
+        // No monitors known
+        assert_eq!(clamp_rect_to_monitors(rect(-500, -50), &[]), rect(-500, -50));
+    }
+
+    #[test]
+    fn test_clamp_oversized_rect() {
+        let monitors = [monitor(0, 0, 0, 800, 600)];
+        let rect = ScreenRect { x: 100, y: 100, width: 1000, height: 700 };
+        assert_eq!(clamp_rect_to_monitors(rect, &monitors), ScreenRect { x: 0, y: 0, ..rect });
+    }
+
+    #[test]
+    fn test_snap_to_grid() {
+        assert_eq!((snap_to_grid(103, 10), snap_to_grid(97, 10)), (100, 100));
+        assert_eq!(snap_to_grid(105, 10), 110);
+        assert_eq!(snap_to_grid(-7, 10), -10);
+        assert_eq!(snap_to_grid(103, 0), 103);
+        assert_eq!(snap_to_grid(i32::MAX, 1000), i32::MAX);
+    }
+
+    #[test]
+    fn test_snap_rect() {
+        let screen = ScreenRect { x: 0, y: 0, width: 1920, height: 1080 };
+        let other = ScreenRect { x: 500, y: 500, width: 300, height: 100 };
+        let rect = |x, y| ScreenRect { x, y, width: 400, height: 100 };
+
+        // Near the top-left screen corner
+        assert_eq!(snap_rect(rect(7, 4), &[screen], 10), rect(0, 0));
+        // Near the right screen edge
+        assert_eq!(snap_rect(rect(1512, 300), &[screen], 10), rect(1520, 300));
+        // Next to another overlay: left edge meets its right edge, tops align
+        assert_eq!(snap_rect(rect(805, 492), &[screen, other], 10), rect(800, 500));
+        // Out of range or disabled
+        assert_eq!(snap_rect(rect(50, 50), &[screen, other], 10), rect(50, 50));
+        assert_eq!(snap_rect(rect(7, 4), &[screen], 0), rect(7, 4));
+    }
+
+    #[test]
+    fn test_px_round_half_to_even() {
+        assert_eq!(px_round(0.5), 0);
+        assert_eq!(px_round(1.5), 2);
+        assert_eq!(px_round(2.5), 2);
+        assert_eq!(px_round(-0.5), 0);
+        assert_eq!(px_round(-2.5), -2);
+        assert_eq!(px_round(2.4999), 2);
+        assert_eq!(px_round(2.5001), 3);
+        assert_eq!(px_round(f64::NAN), 0);
+        assert_eq!(px_round(1e12), i32::MAX);
+    }
+
+    #[test]
+    fn test_rect_inset() {
+        let screen = ScreenRect { x: 0, y: 0, width: 1920, height: 1080 };
+        assert_eq!(screen.inset(5.0), ScreenRect { x: 96, y: 54, width: 1728, height: 972 });
+        assert_eq!(screen.inset(0.0), screen);
+        assert_eq!(screen.inset(80.0), ScreenRect { x: 960, y: 540, width: 0, height: 0 });
+    }
+
+    #[test]
+    fn test_capabilities_match_platform() {
+        let caps = capabilities();
+        assert_eq!(caps.supports_click_through(), cfg!(target_os = "windows"));
+        assert_eq!(caps.supports_always_on_top(), caps.supports_click_through());
+    }
+}